@@ -0,0 +1,154 @@
+//! Tiles synthetic days with every tiling method through `Tiler`, and times
+//! the stages of the pipeline on their own. Run with `cargo bench`, or
+//! narrow it down with a filter such as `cargo bench -- "tile/space-filling/1000"`.
+
+use calendar_tiler::{space_filling_tiling_method, tiler, time_respective_tiling_method};
+use calendar_tiler::{get_tiling_method, Event, Tiler, TilerOptions, TilingMethodName};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkGroup, BenchmarkId, Criterion};
+use criterion::measurement::WallTime;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const HOURS_PER_DAY: f64 = 24.0;
+const SEED: u64 = 20;
+const NUMBERS_OF_EVENTS: [usize; 4] = [10, 100, 1_000, 10_000];
+
+// Methods that solve a linear program per overlap cluster grow with its square,
+// so they only run at sizes a day view plausibly holds.
+const LINEAR_PROGRAM_TILING_METHOD_NAMES: [TilingMethodName; 2] = [
+    TilingMethodName::Optimized,
+    TilingMethodName::Auto,
+];
+const LINEAR_PROGRAM_NUMBERS_OF_EVENTS: [usize; 2] = [10, 100];
+
+// The average number of events in progress at any moment of the day.
+const OVERLAP_DENSITIES: [f64; 3] = [1.0, 4.0, 16.0];
+
+struct Day {
+    label: String,
+    events: Vec<Event>,
+    tiler_options: TilerOptions,
+}
+
+fn generate_events(number_of_events: usize, overlap_density: f64) -> Vec<Event> {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let mean_duration = (overlap_density * HOURS_PER_DAY / number_of_events as f64).min(HOURS_PER_DAY);
+
+    (0 .. number_of_events).map(|_| {
+        let start: f64 = rng.gen_range(0.0 .. HOURS_PER_DAY);
+        let duration: f64 = rng.gen_range(0.0 ..= 2.0 * mean_duration);
+
+        Event::new(start, (start + duration).min(HOURS_PER_DAY))
+    }).collect()
+}
+
+fn generate_days(numbers_of_events: &[usize]) -> Vec<Day> {
+    let mut days: Vec<Day> = vec![];
+
+    for &number_of_events in numbers_of_events.iter() {
+        for &overlap_density in OVERLAP_DENSITIES.iter() {
+            days.push(Day {
+                label: format!("{}/density-{}", number_of_events, overlap_density),
+                events: generate_events(number_of_events, overlap_density),
+                tiler_options: TilerOptions::new(0.0001),
+            });
+        }
+    }
+
+    days
+}
+
+fn configure(group: &mut BenchmarkGroup<WallTime>, day: &Day) {
+    group.sample_size(if day.events.len() >= 1_000 { 10 } else { 100 });
+}
+
+fn bench_tiling_method(group: &mut BenchmarkGroup<WallTime>, day: &Day, tiling_method_name: TilingMethodName) {
+    group.bench_function(BenchmarkId::new(tiling_method_name.name(), &day.label), |b| {
+        b.iter_batched(
+            || Tiler::new(day.events.clone(), day.tiler_options, get_tiling_method(tiling_method_name)).unwrap(),
+            |mut tiler| {
+                tiler.tile().unwrap();
+
+                tiler
+            },
+            BatchSize::LargeInput
+        )
+    });
+}
+
+fn bench_tiling_methods(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tile");
+
+    for day in generate_days(&NUMBERS_OF_EVENTS).iter() {
+        configure(&mut group, day);
+
+        for &tiling_method_name in TilingMethodName::ALL.iter() {
+            if !LINEAR_PROGRAM_TILING_METHOD_NAMES.contains(&tiling_method_name) {
+                bench_tiling_method(&mut group, day, tiling_method_name);
+            }
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_linear_program_tiling_methods(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tile-linear-program");
+
+    for day in generate_days(&LINEAR_PROGRAM_NUMBERS_OF_EVENTS).iter() {
+        configure(&mut group, day);
+
+        for &tiling_method_name in LINEAR_PROGRAM_TILING_METHOD_NAMES.iter() {
+            bench_tiling_method(&mut group, day, tiling_method_name);
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_stages(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stage");
+
+    for day in generate_days(&NUMBERS_OF_EVENTS).iter() {
+        configure(&mut group, day);
+
+        // Validates and sorts the events.
+        group.bench_function(BenchmarkId::new("new", &day.label), |b| {
+            b.iter(|| Tiler::new(black_box(day.events.clone()), day.tiler_options, get_tiling_method(TilingMethodName::Basic)))
+        });
+
+        let tiler = Tiler::new(day.events.clone(), day.tiler_options, get_tiling_method(TilingMethodName::Basic)).unwrap();
+
+        group.bench_function(BenchmarkId::new("sort_events", &day.label), |b| {
+            b.iter(|| tiler::sort_events(black_box(&day.events), &day.tiler_options))
+        });
+
+        group.bench_function(BenchmarkId::new("generate_columns", &day.label), |b| {
+            b.iter(|| tiler::generate_columns(black_box(&tiler.sorted_events), &tiler.tiler_options))
+        });
+
+        group.bench_function(BenchmarkId::new("generate_alignment", &day.label), |b| {
+            b.iter(|| time_respective_tiling_method::generate_alignment(black_box(&tiler.sorted_events)))
+        });
+
+        let columns = tiler::generate_columns(&tiler.sorted_events, &tiler.tiler_options);
+        let mut dag_builder = space_filling_tiling_method::build_dag_builder(&tiler.sorted_events, &columns);
+        let mut tiles = tiler.tiles.clone();
+
+        group.bench_function(BenchmarkId::new("update_tiles/space-filling", &day.label), |b| {
+            b.iter(|| dag_builder.update_tiles(black_box(&mut tiles)))
+        });
+
+        let alignment = time_respective_tiling_method::generate_alignment(&tiler.sorted_events);
+        let mut dag_builder = time_respective_tiling_method::build_dag_builder(&alignment);
+
+        group.bench_function(BenchmarkId::new("update_tiles/time-respective", &day.label), |b| {
+            b.iter(|| dag_builder.update_tiles(black_box(&mut tiles)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_tiling_methods, bench_linear_program_tiling_methods, bench_stages);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+use crate::event;
+use crate::tile;
+use crate::tiler;
+use crate::tiler_options;
+
+/// Where an all-day event sits in the horizontal lanes above the day grid.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct AllDayTile {
+  pub original_index: usize,
+  /// Lane counted from the top. Every lane spans the full width of the day.
+  pub lane: usize,
+}
+
+/// Everything a day view draws: the timed events tiled in the day grid, and
+/// the all-day events stacked in lanes above it.
+#[derive(PartialEq, Clone, Debug)]
+pub struct DayLayout {
+  /// Tiles of the timed events, keyed by original index.
+  pub tiles: Vec<(usize, tile::Tile)>,
+  pub all_day_tiles: Vec<AllDayTile>,
+}
+
+/// Gives every all-day event among `events` a lane of its own, since each one
+/// spans the whole day, in the order the day grid sorts events: by start, then
+/// longest first. Returns the all-day tiles in lane order.
+pub fn layout_all_day_events(
+  events: &[event::Event],
+  tiler_options: &tiler_options::TilerOptions
+) -> Vec<AllDayTile> {
+  let original_indices: Vec<usize> = events.iter()
+                                           .enumerate()
+                                           .filter(|(_, event)| event.all_day)
+                                           .map(|(original_index, _)| original_index)
+                                           .collect();
+  let all_day_events: Vec<event::Event> = original_indices.iter().map(|&original_index| events[original_index]).collect();
+
+  tiler::sort_events(&all_day_events, tiler_options).iter().enumerate().map(|(lane, sorted_event)| AllDayTile {
+    original_index: original_indices[sorted_event.original_index],
+    lane
+  }).collect()
+}
@@ -0,0 +1,109 @@
+use std::cmp::Ordering;
+use crate::event;
+use crate::sorted_event;
+use crate::tile;
+use crate::tiling_method;
+use crate::tiler_options;
+use crate::tiler_error;
+use crate::overlap_cluster;
+use crate::layout_quality;
+
+// Cascade is left out, since its stacked tiles overlap on purpose and would
+// score as covering more of the day than they show.
+const CANDIDATES: [tiling_method::TilingMethodName; 6] = [
+  tiling_method::TilingMethodName::Basic,
+  tiling_method::TilingMethodName::SpaceFilling,
+  tiling_method::TilingMethodName::TimeRespective,
+  tiling_method::TilingMethodName::IntervalColoring,
+  tiling_method::TilingMethodName::ExpandRight,
+  tiling_method::TilingMethodName::Optimized
+];
+
+/// Tiles every overlap cluster with each side by side tiling method, and keeps
+/// the layout that scores best by `objective`. Ties go to the earlier method.
+/// A method that fails on a cluster, as the optimized method does when
+/// `TilerOptions::min_width` is too wide for it, is left out of the running
+/// there, and the error is only returned when every method fails. A `strict`
+/// method returns the first error any method gives instead.
+pub struct AutoTilingMethod {
+  pub objective: layout_quality::LayoutObjective,
+  pub strict: bool,
+  tiling_methods: Vec<Box<dyn tiling_method::TilingMethod>>,
+}
+
+impl AutoTilingMethod {
+  pub fn new() -> AutoTilingMethod {
+    AutoTilingMethod::with_objective(layout_quality::LayoutObjective::default())
+  }
+
+  pub fn with_objective(
+    objective: layout_quality::LayoutObjective
+  ) -> AutoTilingMethod {
+    AutoTilingMethod {
+      objective,
+      strict: false,
+      tiling_methods: CANDIDATES.iter().map(|&tiling_method_name| tiling_method::get_tiling_method(tiling_method_name)).collect()
+    }
+  }
+
+  pub fn strict() -> AutoTilingMethod {
+    AutoTilingMethod { strict: true, ..AutoTilingMethod::new() }
+  }
+}
+
+impl Default for AutoTilingMethod {
+  fn default() -> AutoTilingMethod {
+    AutoTilingMethod::new()
+  }
+}
+
+impl tiling_method::TilingMethod for AutoTilingMethod {
+  fn tile(
+    &self,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
+    tiler_options: &tiler_options::TilerOptions
+  ) -> Result<(), tiler_error::TilerError> {
+    for range in overlap_cluster::get_cluster_ranges(sorted_events, tiler_options) {
+      let offset = sorted_events[range.start].sorted_index;
+      let cluster: Vec<sorted_event::SortedEvent> = sorted_events[range].iter().enumerate().map(|(i, sorted_event)| {
+        sorted_event::SortedEvent { sorted_index: i, ..*sorted_event }
+      }).collect();
+      let events: Vec<event::Event> = cluster.iter().map(|sorted_event| sorted_event.event).collect();
+      let mut best: Option<(Vec<tile::Tile>, layout_quality::LayoutQuality)> = None;
+      let mut result: Result<(), tiler_error::TilerError> = Ok(());
+
+      for tiling_method in self.tiling_methods.iter() {
+        let mut cluster_tiles = tiles[offset .. offset + cluster.len()].to_vec();
+
+        match tiling_method.tile(&mut cluster_tiles, &cluster, tiler_options) {
+          Err(error) if self.strict => return Err(error),
+          Err(error) => {
+            result = Err(error);
+            continue;
+          },
+          Ok(()) => {}
+        }
+
+        let quality = layout_quality::score_layout(&cluster_tiles, &events, tiler_options);
+        let is_better = match &best {
+          Some((_, best_quality)) => {
+            quality.compare(best_quality, self.objective, tiler_options.error_bound) == Ordering::Greater
+          },
+          None => true
+        };
+
+        if is_better {
+          best = Some((cluster_tiles, quality));
+        }
+      }
+
+      match best {
+        Some((cluster_tiles, _)) => tiles[offset .. offset + cluster.len()].copy_from_slice(&cluster_tiles),
+        None => return result
+      }
+    }
+
+    Ok(())
+  }
+}
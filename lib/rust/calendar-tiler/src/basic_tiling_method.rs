@@ -3,21 +3,23 @@ use crate::tile;
 use crate::tiler;
 use crate::tiling_method;
 use crate::tiler_options;
+use crate::tiler_error;
 
+#[derive(Default)]
 pub struct BasicTilingMethod;
 
 fn tile_from_columns(
-  columns: &Vec<Vec<sorted_event::SortedEvent>>,
-  tiles: &mut Vec<tile::Tile>,
+  columns: &[Vec<sorted_event::SortedEvent>],
+  tiles: &mut [tile::Tile],
 ) {
   let columns_length = columns.len();
   let column_dx = 1.0 / (columns_length as f64);
 
-  for i in (0 .. columns_length).rev() {
+  for (i, column) in columns.iter().enumerate().rev() {
     let column_x = (i as f64) / (columns_length as f64);
 
-    for j in (0 .. columns[i].len()).rev() {
-      let tile_index = columns[i][j].sorted_index;
+    for sorted_event in column.iter().rev() {
+      let tile_index = sorted_event.sorted_index;
 
       tiles[tile_index].x = column_x;
       tiles[tile_index].dx = column_dx;
@@ -34,16 +36,23 @@ impl BasicTilingMethod {
 impl tiling_method::TilingMethod for BasicTilingMethod {
   fn tile(
     &self,
-    tiles: &mut Vec<tile::Tile>,
-    sorted_events: &Vec<sorted_event::SortedEvent>,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
     tiler_options: &tiler_options::TilerOptions
-  ) {
-    if sorted_events.len() == 0 {
-      return;
+  ) -> Result<(), tiler_error::TilerError> {
+    if sorted_events.is_empty() {
+      return Ok(());
     }
 
     let columns = tiler::generate_columns(sorted_events, tiler_options);
 
     tile_from_columns(&columns, tiles);
+
+    Ok(())
+  }
+
+  // Every column is as wide as the busiest moment of the whole day allows.
+  fn tiles_clusters_independently(&self) -> bool {
+    false
   }
 }
@@ -0,0 +1,66 @@
+use crate::sorted_event;
+use crate::tile;
+use crate::tiler;
+use crate::tiling_method;
+use crate::tiler_options;
+use crate::tiler_error;
+use crate::overlap_cluster;
+
+// Every cascaded tile keeps at least a tenth of the width, whatever the options.
+const MAX_CASCADE_INDENT: f64 = 0.9;
+
+fn tile_cluster(
+  cluster: &[sorted_event::SortedEvent],
+  tiles: &mut [tile::Tile],
+  tiler_options: &tiler_options::TilerOptions
+) {
+  // An event's depth is its first-fit column, so events at the same depth never
+  // overlap. The step shrinks when needed to keep the deepest event in bounds.
+  let columns = tiler::generate_columns(cluster, tiler_options);
+  let max_depth = columns.len() - 1;
+  let max_indent = tiler_options.cascade_max_indent.clamp(0.0, MAX_CASCADE_INDENT);
+
+  let indent_step = if max_depth == 0 {
+    0.0
+  } else {
+    tiler_options.cascade_indent_step.max(0.0).min(max_indent / (max_depth as f64))
+  };
+
+  for (depth, column) in columns.iter().enumerate() {
+    for sorted_event in column.iter() {
+      let tile = &mut tiles[sorted_event.sorted_index];
+
+      tile.x = (depth as f64) * indent_step;
+      tile.dx = 1.0 - tile.x;
+    }
+  }
+
+  // Later events are stacked above the ones they start during.
+  for (z, sorted_event) in cluster.iter().enumerate() {
+    tiles[sorted_event.sorted_index].z = z as u32;
+  }
+}
+
+#[derive(Default)]
+pub struct CascadeTilingMethod;
+
+impl CascadeTilingMethod {
+  pub fn new() -> CascadeTilingMethod {
+    CascadeTilingMethod {}
+  }
+}
+
+impl tiling_method::TilingMethod for CascadeTilingMethod {
+  fn tile(
+    &self,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
+    tiler_options: &tiler_options::TilerOptions
+  ) -> Result<(), tiler_error::TilerError> {
+    for range in overlap_cluster::get_cluster_ranges(sorted_events, tiler_options) {
+      tile_cluster(&sorted_events[range], tiles, tiler_options);
+    }
+
+    Ok(())
+  }
+}
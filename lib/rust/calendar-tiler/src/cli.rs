@@ -0,0 +1,167 @@
+use calendar_tiler::{LayoutObjective, TilingMethodName};
+use std::fmt::{self, Formatter, Display};
+use std::str::FromStr;
+
+pub const USAGE: &str = "\
+Usage: calendar-tiler [OPTIONS]
+
+Tiles calendar events read as JSON or iCalendar, or a randomly generated day when no input is given.
+iCalendar input, --date and --time-zone need the binary built with `--features ics`.
+
+Options:
+    --method <NAME>         Tiling method to use [default: basic]
+    --objective <NAME>      Measure --method auto ranks layouts by, one of min-width, area-utilization,
+                            width-variance, narrow-tile-count [default: min-width]
+    --error-bound <NUMBER>  Tolerance used when comparing event times [default: 0.0001]
+    --input <PATH>          JSON array of events or iCalendar file to tile, `-` reads from stdin
+    --date <YYYY-MM-DD>     Day to tile the events of an iCalendar input on
+    --time-zone <NAME>      IANA time zone the day is shown in [default: UTC]
+    --output <PATH>         File to write tiles to [default: stdout]
+    --format <FORMAT>       Output format, one of json, debug, svg [default: json]
+    --seed <NUMBER>         Seed for randomly generated events
+    --count <NUMBER>        Number of randomly generated events
+    --list-methods          Describe the available tiling methods
+    --help                  Print this message";
+
+const DEFAULT_ERROR_BOUND: f64 = 0.0001;
+const DEFAULT_TIME_ZONE: &str = "UTC";
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Format {
+    Json,
+    Debug,
+    Svg,
+}
+
+impl FromStr for Format {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Format, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "debug" => Ok(Format::Debug),
+            "svg" => Ok(Format::Svg),
+            _ => Err(CliError::InvalidValue("--format".to_string(), s.to_string()))
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Options {
+    pub tiling_method_name: TilingMethodName,
+    pub objective: Option<LayoutObjective>,
+    pub error_bound: f64,
+    pub input: Option<String>,
+    pub output: Option<String>,
+    pub date: Option<String>,
+    pub time_zone: String,
+    pub format: Format,
+    pub seed: Option<u64>,
+    pub count: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            tiling_method_name: TilingMethodName::Basic,
+            objective: None,
+            error_bound: DEFAULT_ERROR_BOUND,
+            input: None,
+            output: None,
+            date: None,
+            time_zone: DEFAULT_TIME_ZONE.to_string(),
+            format: Format::Json,
+            seed: None,
+            count: None,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Command {
+    Tile(Options),
+    ListMethods,
+    Help,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum CliError {
+    UnknownFlag(String),
+    MissingValue(String),
+    InvalidValue(String, String),
+    ConflictingFlags(String, String),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CliError::UnknownFlag(flag) => write!(f, "unknown flag: {}", flag),
+            CliError::MissingValue(flag) => write!(f, "{} requires a value", flag),
+            CliError::InvalidValue(flag, value) => write!(f, "invalid value for {}: {}", flag, value),
+            CliError::ConflictingFlags(a, b) => write!(f, "{} cannot be combined with {}", a, b),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+fn parse_value<T: FromStr>(flag: &str, value: &str) -> Result<T, CliError> {
+    value.parse::<T>().map_err(|_| CliError::InvalidValue(flag.to_string(), value.to_string()))
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, CliError> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None)
+        };
+
+        match flag.as_str() {
+            "--help" => return Ok(Command::Help),
+            "--list-methods" => return Ok(Command::ListMethods),
+            "--method" | "--objective" | "--error-bound" | "--input" | "--output" | "--date" | "--time-zone" | "--format" | "--seed"
+            | "--count" => {}
+            _ => return Err(CliError::UnknownFlag(flag))
+        }
+
+        let value = match inline_value.or_else(|| args.next()) {
+            Some(value) => value,
+            None => return Err(CliError::MissingValue(flag))
+        };
+
+        match flag.as_str() {
+            "--method" => options.tiling_method_name = parse_value(&flag, &value)?,
+            "--objective" => options.objective = Some(parse_value(&flag, &value)?),
+            "--error-bound" => options.error_bound = parse_value(&flag, &value)?,
+            "--input" => options.input = Some(value),
+            "--output" => options.output = Some(value),
+            "--date" => options.date = Some(value),
+            "--time-zone" => options.time_zone = value,
+            "--format" => options.format = value.parse()?,
+            "--seed" => options.seed = Some(parse_value(&flag, &value)?),
+            _ => options.count = Some(parse_value(&flag, &value)?)
+        }
+    }
+
+    if options.objective.is_some() && options.tiling_method_name != TilingMethodName::Auto {
+        return Err(CliError::ConflictingFlags(
+            "--objective".to_string(),
+            format!("--method {}", options.tiling_method_name)
+        ));
+    }
+
+    if options.input.is_some() {
+        if options.seed.is_some() {
+            return Err(CliError::ConflictingFlags("--seed".to_string(), "--input".to_string()));
+        }
+
+        if options.count.is_some() {
+            return Err(CliError::ConflictingFlags("--count".to_string(), "--input".to_string()));
+        }
+    }
+
+    Ok(Command::Tile(options))
+}
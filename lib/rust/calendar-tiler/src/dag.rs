@@ -1,5 +1,10 @@
+use std::collections::HashSet;
+
 pub struct DirectedAcyclicGraph {
   edges: Vec<Vec<usize>>,
+  // The same edges as `(from_vertex, to_vertex)` pairs, so `add_edge` checks
+  // for duplicates without scanning either vertex's edges.
+  edge_set: HashSet<(usize, usize)>,
   sorted_vertices: Vec<usize>,
 }
 
@@ -29,7 +34,7 @@ impl DirectedAcyclicGraph {
         }
       ];
 
-      while stack.len() > 0 {
+      while !stack.is_empty() {
         let stack_length = stack.len();
         last_stack_item = &mut stack[stack_length - 1];
 
@@ -62,17 +67,32 @@ impl DirectedAcyclicGraph {
     from_vertex: usize,
     to_vertex: usize
   ) -> bool {
-    if from_vertex >= self.edges.len()
+    if from_vertex == to_vertex
+      || from_vertex >= self.edges.len()
       || to_vertex >= self.edges.len()
-      || self.edges[from_vertex].iter().any(|vertex| *vertex == to_vertex)
-      || self.edges[to_vertex].iter().any(|vertex| *vertex == from_vertex) {
+      || self.edge_set.contains(&(from_vertex, to_vertex))
+      || self.edge_set.contains(&(to_vertex, from_vertex)) {
       return false;
     }
-    
+
     self.edges[from_vertex].push(to_vertex);
+    self.edge_set.insert((from_vertex, to_vertex));
+
+    true
+  }
 
-    return true;
-  } 
+  pub fn get_edges(
+    &self,
+    vertex: usize
+  ) -> &[usize] {
+    &self.edges[vertex]
+  }
+
+  /// Vertices in the order the last `topological_sort` finished them, so every
+  /// vertex comes after all of the vertices it has edges to.
+  pub fn get_sorted_vertices(&self) -> &[usize] {
+    &self.sorted_vertices
+  }
 
   pub fn new(
     number_of_vertices: u128
@@ -81,83 +101,50 @@ impl DirectedAcyclicGraph {
 
     DirectedAcyclicGraph {
       edges,
+      edge_set: HashSet::new(),
       sorted_vertices: vec![]
     }
   }
 
-  fn build_paths_through_vertex(
-    &mut self,
-    vertex: usize,
-    vertices: &mut Vec<Option<usize>>
-  ) -> Vec<Option<usize>> {
-    let mut paths: Vec<Option<usize>> = vec![None; self.edges.len()];
-
-    self.topological_sort();
-    
-    paths[vertex] = Some(0);
-
-    for i in (0 .. self.sorted_vertices.len()).rev() {
-      let from_vertex = self.sorted_vertices[i];
-
-      if let Some(paths_from_vertex) = paths[from_vertex] {
-        for j in 0 .. self.edges[from_vertex].len() {
-          let mut set_path_and_vertex = false;  
-          let to_vertex = self.edges[from_vertex][j];      
-
-          match paths[to_vertex] {
-            Some(paths_to_vertex) => {
-              if paths_to_vertex <= paths_from_vertex {
-                set_path_and_vertex = true;
-              }
-            },
-            None => {
-              set_path_and_vertex = true;
-            }
-          }
+  /// Number of vertices on the longest path starting at each vertex. Requires
+  /// an up to date `topological_sort`, and visits every edge once.
+  pub fn get_longest_path_lengths(&self) -> Vec<usize> {
+    let mut longest_path_lengths: Vec<usize> = vec![1; self.edges.len()];
 
-          if set_path_and_vertex {
-            paths[to_vertex].replace(paths_from_vertex + 1);
-            vertices[to_vertex].replace(from_vertex);
-          }
-        }
+    for &from_vertex in self.sorted_vertices.iter() {
+      for &to_vertex in self.edges[from_vertex].iter() {
+        longest_path_lengths[from_vertex] = longest_path_lengths[from_vertex].max(
+          longest_path_lengths[to_vertex] + 1
+        );
       }
     }
 
-    paths
+    longest_path_lengths
   }
 
-  pub fn get_longest_path_through_vertex(
-    &mut self,
-    vertex: usize
-  ) -> Vec<usize> {
-    let mut longest_path: Vec<usize> = vec![];
-    let mut longest_path_length: usize = 0;
-    let mut longest_path_index: Option<usize> = None;
-    let mut vertices: Vec<Option<usize>> = vec![];
-    let paths = self.build_paths_through_vertex(vertex, &mut vertices);
-
-    for i in (0 .. self.edges.len()).rev() {
-      if let Some(path_length) = paths[i] {
-        if path_length >= longest_path_length {
-          longest_path_length = path_length;
-          longest_path_index.replace(i);
-        }
-      }
-    }
-
-    if let Some(longest_path_index_usize) = longest_path_index {
-      longest_path.push(longest_path_index_usize);
+  /// Number of vertices on the longest path ending at each vertex. Requires
+  /// an up to date `topological_sort`, and visits every edge once.
+  pub fn get_longest_path_lengths_to(&self) -> Vec<usize> {
+    let mut longest_path_lengths: Vec<usize> = vec![1; self.edges.len()];
 
-      for _j in 0 .. longest_path_length {
-        if let Some(longest_vertix_length) = vertices[longest_path_index_usize] {
-          longest_path.push(longest_vertix_length);
-          longest_path_index.replace(longest_vertix_length);
-        }
+    for &from_vertex in self.sorted_vertices.iter().rev() {
+      for &to_vertex in self.edges[from_vertex].iter() {
+        longest_path_lengths[to_vertex] = longest_path_lengths[to_vertex].max(
+          longest_path_lengths[from_vertex] + 1
+        );
       }
     }
 
-    longest_path.pop();
+    longest_path_lengths
+  }
 
-    longest_path
+  /// Number of vertices on the longest path through each vertex, joining the
+  /// longest paths ending and starting there.
+  pub fn get_longest_path_lengths_through(&self) -> Vec<usize> {
+    self.get_longest_path_lengths_to()
+        .into_iter()
+        .zip(self.get_longest_path_lengths())
+        .map(|(length_to, length_from)| length_to + length_from - 1)
+        .collect()
   }
 }
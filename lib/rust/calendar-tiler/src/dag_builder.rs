@@ -1,7 +1,16 @@
 use crate::dag;
 use crate::tile;
-use std::collections::HashMap;
 
+/// Lays out the tiles of a DAG whose edges point from each tile to the tiles
+/// it overlaps on its right.
+///
+/// Placement is topological rather than path based. Each tile starts at the
+/// right edge of its left neighbours and takes an even share of the width left
+/// for the longest forward path from it, so a tile is never narrower than one
+/// over the longest path through it. This replaces the original model, which
+/// laid out the longest paths one at a time with blocking and non-blocking
+/// widths. A path there only saw its own vertices, so tiles could be squeezed
+/// to zero width or overlap neighbours that other paths had already placed.
 pub struct DagBuilder {
   pub forward_dag: dag::DirectedAcyclicGraph,
   pub backward_dag: dag::DirectedAcyclicGraph,
@@ -17,129 +26,49 @@ impl DagBuilder {
     }
   }
 
-  fn generate_longest_vertex_paths(
-    &mut self,
-    tiles: &mut Vec<tile::Tile>
-  ) -> Vec<Vec<usize>> {
-    let mut path_map: HashMap<String, bool> = HashMap::new();
-    let mut longest_paths: Vec<Vec<usize>> = vec![];
-
-    for i in 0 .. tiles.len() {
-      let mut forward_path = self.forward_dag.get_longest_path_through_vertex(i);
-
-      forward_path.reverse();
-
-      let path: Vec<usize> = [
-        self.backward_dag.get_longest_path_through_vertex(i),
-        vec![i],
-        forward_path
-      ].concat();
-
-      let path_key: String = path.iter()
-                                 .map(|a| (*a).to_string())
-                                 .collect::<Vec<String>>()
-                                 .join(",");
-
-      let existing_key = path_map.get(&path_key);
-
-      if existing_key == None {
-        path_map.insert(path_key, true);
-        longest_paths.push(path);
-      }
-    }
-
-    longest_paths.sort_by(|a, b| if a.len() > b.len() {
-      std::cmp::Ordering::Greater
-    } else if b.len() < a.len() {
-      std::cmp::Ordering::Less
-    } else {
-      std::cmp::Ordering::Equal
-    });
-
-    longest_paths
-  }
-
-  fn calculate_blocking_dx(
-    tiles: &Vec<tile::Tile>,
-    path: &Vec<usize>,
+  fn set_position(
+    &self,
+    tiles: &mut [tile::Tile],
     vertex: usize,
-    x: f64
-  ) -> Option<f64> {
-    for i in (vertex + 1) .. path.len() {
-      if tiles[path[i]].x != tile::X_SENTINEL {
-        return Some(tiles[path[i]].x - x / ((i - vertex) as f64));
-      }
-    }
-
-    None
-  }
+    forward_length: usize
+  ) {
+    let mut x: f64 = 0f64;
 
-  fn calculate_non_blocking_dx(
-    tiles: &Vec<tile::Tile>,
-    path: &Vec<usize>,
-  ) -> f64 {
-    let mut unset: usize = 0;
-    let mut dx: f64 = 0f64;
+    for &backward_vertex in self.backward_dag.get_edges(vertex).iter() {
+      let tile = tiles[backward_vertex];
 
-    for i in 9 .. path.len() {
-      if tiles[path[i]].dx < tile::DX_SENTINEL {
-        dx += tiles[path[i]].dx;
-      } else {
-        unset += 1;
-      }
+      x = x.max(tile.x + tile.dx);
     }
 
-    if unset == 0 {
-      unset = 1;
-    }
-
-    (1f64 - dx) / (unset as f64)
+    tiles[vertex].x = x;
+    tiles[vertex].dx = (1f64 - x) / (forward_length as f64);
   }
 
-  fn set_position(
-    tiles: &mut Vec<tile::Tile>,
-    path: &Vec<usize>,
-    vertex: usize
+  /// Records that `from_vertex` overlaps `to_vertex` and sits to its left.
+  pub fn add_edge(
+    &mut self,
+    from_vertex: usize,
+    to_vertex: usize
   ) {
-    let previous_vertex: Option<&usize> = path.get(vertex - 1);
-
-    let x: f64 = match previous_vertex {
-      None => {
-        0f64
-      },
-      Some(previous_vertex_usize) => {
-        let tile = tiles[*previous_vertex_usize];
-
-        tile.x + tile.dx
-      }
-    };
-
-    let dx: Option<f64> = DagBuilder::calculate_blocking_dx(tiles, path, vertex, x);
-
-    if tiles[path[vertex]].dx == tile::DX_SENTINEL {
-      tiles[path[vertex]].x = x;
-
-      tiles[path[vertex]].dx = match dx {
-        Some(dx_f64) => {
-          dx_f64
-        },
-        None => {
-          DagBuilder::calculate_non_blocking_dx(tiles, path)
-        }
-      };
+    if self.forward_dag.add_edge(from_vertex, to_vertex) {
+      self.backward_dag.add_edge(to_vertex, from_vertex);
     }
   }
 
   pub fn update_tiles(
     &mut self,
-    tiles: &mut Vec<tile::Tile>
+    tiles: &mut [tile::Tile]
   ) {
-    let longest_paths = self.generate_longest_vertex_paths(tiles);
+    // Sorting once serves both passes. Longest forward paths are built from the
+    // right, then every vertex is placed from the left, right of everything it
+    // overlaps there, splitting the space that remains evenly with the longest
+    // path still ahead of it.
+    self.forward_dag.topological_sort();
+
+    let forward_lengths = self.forward_dag.get_longest_path_lengths();
 
-    for i in 0 .. longest_paths.len() {
-      for j in 0 .. longest_paths[i].len() {
-        DagBuilder::set_position(tiles, &longest_paths[i], j);
-      }
+    for &vertex in self.forward_dag.get_sorted_vertices().iter().rev() {
+      self.set_position(tiles, vertex, forward_lengths[vertex]);
     }
   }
 }
@@ -1,9 +1,50 @@
 use std::fmt::{self, Formatter, Display};
 
-#[derive(PartialEq, PartialOrd, Copy, Clone)]
+/// Identifies an event in a `Tiler` for as long as it stays there, unlike its
+/// original index, which shifts down when an earlier event is removed.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
+pub struct EventId(pub u64);
+
+/// Build events with `Event::new` or `Event::all_day`, so that adding a field
+/// does not break every caller.
+#[derive(PartialEq, PartialOrd, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct Event {
   pub start: f64,
   pub end: f64,
+  /// Whether the event lasts the whole day, and belongs in the all-day lanes
+  /// instead of the day grid.
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub all_day: bool,
+}
+
+impl Event {
+  /// An event on the day grid.
+  pub fn new(
+    start: f64,
+    end: f64
+  ) -> Event {
+    Event { start, end, all_day: false }
+  }
+
+  /// An event that lasts the whole day, laid out in the all-day lanes.
+  pub fn all_day(
+    start: f64,
+    end: f64
+  ) -> Event {
+    Event { start, end, all_day: true }
+  }
+
+  /// Whether the two events share more than `error_bound` of time, so events
+  /// that only touch do not overlap.
+  pub fn overlaps(
+    &self,
+    other: &Event,
+    error_bound: f64
+  ) -> bool {
+    self.start < other.end - error_bound && other.start < self.end - error_bound
+  }
 }
 
 impl Display for Event {
@@ -11,11 +52,21 @@ impl Display for Event {
     &self, 
     f: &mut Formatter
   ) -> fmt::Result {
-    write!(
+    writeln!(
       f,
-      "start: {:.3} end: {:.3}\n",
+      "start: {:.3} end: {:.3} all day: {}",
       self.start,
-      self.end
+      self.end,
+      self.all_day
     )
   }
 }
+
+impl Display for EventId {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
@@ -0,0 +1,69 @@
+use crate::sorted_event;
+use crate::tile;
+use crate::tiler;
+use crate::tiling_method;
+use crate::tiler_options;
+use crate::tiler_error;
+use crate::overlap_cluster;
+
+fn is_column_free(
+  column: &[sorted_event::SortedEvent],
+  sorted_event: &sorted_event::SortedEvent,
+  tiler_options: &tiler_options::TilerOptions
+) -> bool {
+  for column_sorted_event in column.iter() {
+    if column_sorted_event.event.start >= sorted_event.event.end {
+      break;
+    }
+
+    if column_sorted_event.event.overlaps(&sorted_event.event, tiler_options.error_bound) {
+      return false;
+    }
+  }
+
+  true
+}
+
+fn tile_cluster(
+  cluster: &[sorted_event::SortedEvent],
+  tiles: &mut [tile::Tile],
+  tiler_options: &tiler_options::TilerOptions
+) {
+  let columns = tiler::generate_columns(cluster, tiler_options);
+  let column_dx = 1.0 / (columns.len() as f64);
+
+  for (i, column) in columns.iter().enumerate() {
+    for sorted_event in column.iter() {
+      let span = 1 + columns[(i + 1) ..].iter()
+                                          .take_while(|later_column| is_column_free(later_column, sorted_event, tiler_options))
+                                          .count();
+
+      tiles[sorted_event.sorted_index].x = (i as f64) * column_dx;
+      tiles[sorted_event.sorted_index].dx = (span as f64) * column_dx;
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct ExpandRightTilingMethod;
+
+impl ExpandRightTilingMethod {
+  pub fn new() -> ExpandRightTilingMethod {
+    ExpandRightTilingMethod {}
+  }
+}
+
+impl tiling_method::TilingMethod for ExpandRightTilingMethod {
+  fn tile(
+    &self,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
+    tiler_options: &tiler_options::TilerOptions
+  ) -> Result<(), tiler_error::TilerError> {
+    for range in overlap_cluster::get_cluster_ranges(sorted_events, tiler_options) {
+      tile_cluster(&sorted_events[range], tiles, tiler_options);
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,1009 @@
+use crate::event;
+use crate::json;
+use crate::time;
+use crate::week_tiler;
+use jiff::{civil, tz, Span, Timestamp, ToSpan, Zoned};
+use serde_json::{json, Value};
+use std::fmt::{self, Formatter, Display};
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum IcsError {
+  /// A content line with no value, or a component ended out of order.
+  InvalidLine {
+    line_number: usize,
+  },
+  InvalidValue {
+    line_number: usize,
+    name: String,
+    value: String,
+  },
+  MissingStart {
+    line_number: usize,
+  },
+  UnclosedComponent {
+    line_number: usize,
+    name: String,
+  },
+  UnknownTimeZone {
+    line_number: usize,
+    name: String,
+  },
+  /// An event whose times fall outside the range the tz database covers.
+  OutOfRange {
+    line_number: usize,
+  },
+  /// An RRULE with parts `get_events_in_range` does not expand, such as
+  /// BYSETPOS or a frequency under a day.
+  UnsupportedRule {
+    line_number: usize,
+    rule: String,
+  },
+}
+
+impl Display for IcsError {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    match self {
+      IcsError::InvalidLine { line_number } => write!(f, "line {} is not a valid content line", line_number),
+      IcsError::InvalidValue { line_number, name, value } => write!(
+        f,
+        "line {}: invalid value for {}: {:?}",
+        line_number,
+        name,
+        value
+      ),
+      IcsError::MissingStart { line_number } => write!(
+        f,
+        "the event starting on line {} has no DTSTART",
+        line_number
+      ),
+      IcsError::UnclosedComponent { line_number, name } => write!(
+        f,
+        "the {} starting on line {} is never ended",
+        name,
+        line_number
+      ),
+      IcsError::UnknownTimeZone { line_number, name } => write!(
+        f,
+        "the event starting on line {} uses the unknown time zone {:?}",
+        line_number,
+        name
+      ),
+      IcsError::OutOfRange { line_number } => write!(
+        f,
+        "the event starting on line {} is out of the supported time range",
+        line_number
+      ),
+      IcsError::UnsupportedRule { line_number, rule } => write!(
+        f,
+        "the event starting on line {} repeats by the unsupported rule {:?}",
+        line_number,
+        rule
+      ),
+    }
+  }
+}
+
+impl std::error::Error for IcsError {}
+
+/// A DTSTART or DTEND value.
+#[derive(PartialEq, Clone, Debug)]
+pub enum IcsTime {
+  /// A whole day, as all-day events give it.
+  Date(civil::Date),
+  /// A wall-clock time in whatever time zone the calendar is shown in.
+  Floating(civil::DateTime),
+  /// A wall-clock time in UTC.
+  Utc(civil::DateTime),
+  /// A wall-clock time in the time zone named by its TZID.
+  Zoned {
+    date_time: civil::DateTime,
+    time_zone_name: String,
+    /// The rules the file's VTIMEZONE gives for the TZID, used when the tz
+    /// database does not know the name, as with Windows zone names.
+    time_zone: Option<tz::TimeZone>,
+  },
+}
+
+/// A VEVENT as it appears in the file, before its times are resolved.
+#[derive(Clone, Debug)]
+pub struct IcsEvent {
+  pub uid: Option<String>,
+  pub summary: Option<String>,
+  pub start: IcsTime,
+  pub end: Option<IcsTime>,
+  pub duration: Option<Span>,
+  /// The RRULE the event repeats by, as the file gives it.
+  pub rule: Option<String>,
+  /// Starts of occurrences beyond those of the rule, from RDATE.
+  pub extra_starts: Vec<IcsTime>,
+  /// Starts of occurrences left out, from EXDATE.
+  pub excluded_starts: Vec<IcsTime>,
+  /// The start of the occurrence of a recurring event with the same UID that
+  /// this event replaces, from RECURRENCE-ID.
+  pub recurrence_id: Option<IcsTime>,
+  /// Line of the event's BEGIN:VEVENT, counted from 1.
+  pub line_number: usize,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Frequency {
+  Daily,
+  Weekly,
+  Monthly,
+  Yearly,
+}
+
+/// Where a recurrence stops, from UNTIL. A wall-clock UNTIL is compared in
+/// the wall-clock time of DTSTART.
+enum Until {
+  DateTime(civil::DateTime),
+  Instant(Timestamp),
+}
+
+/// The parts of an RRULE that `get_events_in_range` expands.
+struct RecurrenceRule {
+  frequency: Frequency,
+  interval: i64,
+  count: Option<usize>,
+  until: Option<Until>,
+  by_month: Vec<i8>,
+  by_month_day: Vec<i8>,
+  /// Weekdays, each with the week of the month it falls in, counted from the
+  /// end when negative, or `None` for every one.
+  by_day: Vec<(Option<i8>, civil::Weekday)>,
+  week_start: civil::Weekday,
+}
+
+/// A STANDARD or DAYLIGHT observance of a VTIMEZONE.
+struct Observance {
+  daylight: bool,
+  /// Local time of the first transition, in the offset before it.
+  start: civil::DateTime,
+  offset_to: tz::Offset,
+  name: Option<String>,
+  rule: Option<String>,
+}
+
+struct ContentLine {
+  line_number: usize,
+  name: String,
+  parameters: Vec<(String, String)>,
+  value: String,
+}
+
+impl ContentLine {
+  fn get_parameter(
+    &self,
+    name: &str
+  ) -> Option<&str> {
+    self.parameters.iter().find(|(parameter_name, _)| parameter_name == name).map(|(_, value)| value.as_str())
+  }
+
+  fn invalid_value(&self) -> IcsError {
+    IcsError::InvalidValue {
+      line_number: self.line_number,
+      name: self.name.clone(),
+      value: self.value.clone()
+    }
+  }
+}
+
+/// Joins folded lines back together, keeping the number of the line each one
+/// starts on.
+fn unfold_lines(text: &str) -> Vec<(usize, String)> {
+  let mut lines: Vec<(usize, String)> = vec![];
+
+  for (i, line) in text.lines().enumerate() {
+    match (line.strip_prefix(|c| c == ' ' || c == '\t'), lines.last_mut()) {
+      (Some(continuation), Some((_, unfolded_line))) => unfolded_line.push_str(continuation),
+      _ if line.is_empty() => {},
+      _ => lines.push((i + 1, line.to_string()))
+    }
+  }
+
+  lines
+}
+
+fn parse_content_line(
+  line_number: usize,
+  line: &str
+) -> Result<ContentLine, IcsError> {
+  // Parameter values may quote `;` and `:`, so split outside of quotes only.
+  let mut parts: Vec<String> = vec![String::new()];
+  let mut quoted = false;
+  let mut value_start = None;
+
+  for (i, c) in line.char_indices() {
+    match c {
+      '"' => quoted = !quoted,
+      ';' if !quoted => parts.push(String::new()),
+      ':' if !quoted => {
+        value_start = Some(i + 1);
+        break;
+      },
+      _ => parts.last_mut().unwrap().push(c)
+    }
+  }
+
+  let value_start = value_start.ok_or(IcsError::InvalidLine { line_number })?;
+  let name = parts[0].to_ascii_uppercase();
+  let parameters = parts[1 ..].iter().map(|part| match part.split_once('=') {
+    Some((parameter_name, value)) => (parameter_name.to_ascii_uppercase(), value.to_string()),
+    None => (part.to_ascii_uppercase(), String::new())
+  }).collect();
+
+  if name.is_empty() {
+    return Err(IcsError::InvalidLine { line_number });
+  }
+
+  Ok(ContentLine { line_number, name, parameters, value: line[value_start ..].to_string() })
+}
+
+fn unescape_text(value: &str) -> String {
+  let mut text = String::with_capacity(value.len());
+  let mut chars = value.chars();
+
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      text.push(c);
+      continue;
+    }
+
+    match chars.next() {
+      Some('n') | Some('N') => text.push('\n'),
+      Some(escaped) => text.push(escaped),
+      None => {}
+    }
+  }
+
+  text
+}
+
+fn parse_number<T: std::str::FromStr>(
+  value: &str,
+  range: std::ops::Range<usize>
+) -> Option<T> {
+  value.get(range).filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()))?.parse().ok()
+}
+
+fn parse_date(value: &str) -> Option<civil::Date> {
+  if value.len() != 8 {
+    return None;
+  }
+
+  civil::Date::new(parse_number(value, 0 .. 4)?, parse_number(value, 4 .. 6)?, parse_number(value, 6 .. 8)?).ok()
+}
+
+fn parse_date_time(value: &str) -> Option<civil::DateTime> {
+  if value.len() != 15 || value.as_bytes()[8] != b'T' {
+    return None;
+  }
+
+  let time = civil::Time::new(
+    parse_number(value, 9 .. 11)?,
+    parse_number(value, 11 .. 13)?,
+    // A leap second reads as the last second of its minute.
+    parse_number::<i8>(value, 13 .. 15)?.min(59),
+    0
+  ).ok()?;
+
+  Some(parse_date(value.get(.. 8)?)?.to_datetime(time))
+}
+
+fn parse_time_value(
+  content_line: &ContentLine,
+  value: &str
+) -> Option<IcsTime> {
+  if content_line.get_parameter("VALUE").is_some_and(|value_type| value_type.eq_ignore_ascii_case("DATE")) || value.len() == 8 {
+    parse_date(value).map(IcsTime::Date)
+  } else if let Some(utc_value) = value.strip_suffix('Z') {
+    parse_date_time(utc_value).map(IcsTime::Utc)
+  } else {
+    parse_date_time(value).map(|date_time| match content_line.get_parameter("TZID") {
+      Some(time_zone_name) => IcsTime::Zoned { date_time, time_zone_name: time_zone_name.to_string(), time_zone: None },
+      None => IcsTime::Floating(date_time)
+    })
+  }
+}
+
+fn parse_time(content_line: &ContentLine) -> Result<IcsTime, IcsError> {
+  parse_time_value(content_line, content_line.value.trim()).ok_or_else(|| content_line.invalid_value())
+}
+
+/// Reads the comma-separated times of an RDATE or EXDATE.
+fn parse_times(content_line: &ContentLine) -> Result<Vec<IcsTime>, IcsError> {
+  content_line.value.split(',')
+    .map(|value| parse_time_value(content_line, value.trim()))
+    .collect::<Option<Vec<IcsTime>>>()
+    .ok_or_else(|| content_line.invalid_value())
+}
+
+fn parse_event(
+  line_number: usize,
+  content_lines: &[ContentLine]
+) -> Result<IcsEvent, IcsError> {
+  let mut uid = None;
+  let mut summary = None;
+  let mut start = None;
+  let mut end = None;
+  let mut duration = None;
+  let mut rule = None;
+  let mut extra_starts = vec![];
+  let mut excluded_starts = vec![];
+  let mut recurrence_id = None;
+
+  for content_line in content_lines.iter() {
+    match content_line.name.as_str() {
+      "UID" => uid = Some(unescape_text(&content_line.value)),
+      "SUMMARY" => summary = Some(unescape_text(&content_line.value)),
+      "DTSTART" => start = Some(parse_time(content_line)?),
+      "DTEND" => end = Some(parse_time(content_line)?),
+      "DURATION" => duration = Some(content_line.value.trim().parse().map_err(|_| content_line.invalid_value())?),
+      "RRULE" => rule = Some(content_line.value.trim().to_string()),
+      "RDATE" => extra_starts.extend(parse_times(content_line)?),
+      "EXDATE" => excluded_starts.extend(parse_times(content_line)?),
+      "RECURRENCE-ID" => recurrence_id = Some(parse_time(content_line)?),
+      _ => {}
+    }
+  }
+
+  Ok(IcsEvent {
+    uid,
+    summary,
+    start: start.ok_or(IcsError::MissingStart { line_number })?,
+    end,
+    duration,
+    rule,
+    extra_starts,
+    excluded_starts,
+    recurrence_id,
+    line_number
+  })
+}
+
+/// Reads `+hhmm` or `+hhmmss`, as TZOFFSETFROM and TZOFFSETTO give them.
+fn parse_offset(value: &str) -> Option<tz::Offset> {
+  let sign = match value.get(.. 1)? {
+    "+" => 1,
+    "-" => -1,
+    _ => return None
+  };
+  let seconds = match value.len() {
+    5 => 0,
+    7 => parse_number::<i32>(value, 5 .. 7)?,
+    _ => return None
+  };
+
+  tz::Offset::from_seconds(sign * (parse_number::<i32>(value, 1 .. 3)? * 3600 + parse_number::<i32>(value, 3 .. 5)? * 60 + seconds)).ok()
+}
+
+fn parse_observance(
+  daylight: bool,
+  content_lines: &[ContentLine]
+) -> Option<Observance> {
+  let get_value = |name: &str| content_lines.iter().find(|content_line| content_line.name == name).map(|content_line| content_line.value.trim());
+
+  Some(Observance {
+    daylight,
+    start: parse_date_time(get_value("DTSTART")?)?,
+    offset_to: parse_offset(get_value("TZOFFSETTO")?)?,
+    name: get_value("TZNAME").map(str::to_string),
+    rule: get_value("RRULE").map(str::to_string)
+  })
+}
+
+/// Whether the rule keeps recurring, rather than ending with UNTIL or COUNT.
+fn is_open_ended(rule: &str) -> bool {
+  !rule.split(';').any(|part| {
+    let name = part.split_once('=').map_or(part, |(name, _)| name);
+
+    name.eq_ignore_ascii_case("UNTIL") || name.eq_ignore_ascii_case("COUNT")
+  })
+}
+
+fn parse_weekday(value: &str) -> Option<civil::Weekday> {
+  let weekday = ["SU", "MO", "TU", "WE", "TH", "FR", "SA"].iter().position(|name| name.eq_ignore_ascii_case(value))?;
+
+  civil::Weekday::from_sunday_zero_offset(weekday as i8).ok()
+}
+
+/// Reads a BYDAY value such as `SU`, `2SU` or `-1SU`, with the week of the
+/// month it names, if any.
+fn parse_nth_weekday(value: &str) -> Option<(Option<i8>, civil::Weekday)> {
+  let (ordinal, day) = value.split_at(value.len().checked_sub(2)?);
+  let week = match ordinal {
+    "" => None,
+    _ => Some(ordinal.parse::<i8>().ok().filter(|week| *week != 0 && (-5 ..= 5).contains(week))?)
+  };
+
+  Some((week, parse_weekday(day)?))
+}
+
+/// Writes a yearly transition as a POSIX TZ rule, such as `M3.2.0/2:00:00`.
+/// Only rules on the nth weekday of a month have one.
+fn get_posix_rule(observance: &Observance) -> Option<String> {
+  let mut month = None;
+  let mut nth_weekday = None;
+
+  for part in observance.rule.as_deref()?.split(';') {
+    let (name, value) = part.split_once('=')?;
+
+    match name.to_ascii_uppercase().as_str() {
+      "FREQ" if value.eq_ignore_ascii_case("YEARLY") => {},
+      "INTERVAL" if value == "1" => {},
+      "BYMONTH" => month = Some(value.parse::<i8>().ok().filter(|month| (1 ..= 12).contains(month))?),
+      "BYDAY" => nth_weekday = Some(parse_nth_weekday(value)?),
+      "WKST" => {},
+      _ => return None
+    }
+  }
+
+  // POSIX counts the last week as the fifth, and has no other from the end.
+  let (week, weekday) = match nth_weekday? {
+    (Some(-1), weekday) => (5, weekday),
+    (Some(week @ 1 ..= 4), weekday) => (week, weekday),
+    _ => return None
+  };
+  let time = observance.start.time();
+
+  Some(format!(
+    "M{}.{}.{}/{}:{:02}:{:02}",
+    month?,
+    week,
+    weekday.to_sunday_zero_offset(),
+    time.hour(),
+    time.minute(),
+    time.second()
+  ))
+}
+
+fn get_posix_offset(offset: tz::Offset) -> String {
+  // POSIX counts offsets west of UTC as positive.
+  let seconds = -offset.seconds();
+  let sign = if seconds < 0 { "-" } else { "" };
+
+  format!("{}{}:{:02}:{:02}", sign, seconds.abs() / 3600, seconds.abs() / 60 % 60, seconds.abs() % 60)
+}
+
+fn get_posix_name(
+  observance: &Observance,
+  default_name: &str
+) -> String {
+  match &observance.name {
+    Some(name) if name.len() >= 3 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-') => name.clone(),
+    _ => default_name.to_string()
+  }
+}
+
+/// Builds the time zone a VTIMEZONE describes from its latest standard and
+/// daylight observances, so it follows the current rules but not the zone's
+/// history. Daylight saving time that recurs in a way a POSIX TZ rule cannot
+/// express leaves the zone unknown.
+fn get_vtimezone(observances: &[Observance]) -> Option<tz::TimeZone> {
+  let get_latest = |daylight: bool| observances.iter().filter(|observance| observance.daylight == daylight).max_by_key(|observance| observance.start);
+  let is_recurring = |observance: &&Observance| observance.rule.as_deref().is_some_and(is_open_ended);
+
+  match (get_latest(false).filter(is_recurring), get_latest(true).filter(is_recurring)) {
+    (Some(standard), Some(daylight)) => tz::TimeZone::posix(&format!(
+      "<{}>{}<{}>{},{},{}",
+      get_posix_name(standard, "STD"),
+      get_posix_offset(standard.offset_to),
+      get_posix_name(daylight, "DST"),
+      get_posix_offset(daylight.offset_to),
+      get_posix_rule(daylight)?,
+      get_posix_rule(standard)?
+    )).ok(),
+    _ => get_latest(false).or_else(|| get_latest(true)).map(|observance| tz::TimeZone::fixed(observance.offset_to))
+  }
+}
+
+fn set_vtimezone(
+  ics_time: &mut IcsTime,
+  time_zones: &[(String, tz::TimeZone)]
+) {
+  if let IcsTime::Zoned { time_zone_name, time_zone, .. } = ics_time {
+    *time_zone = time_zones.iter().find(|(name, _)| name == time_zone_name).map(|(_, vtimezone)| vtimezone.clone());
+  }
+}
+
+fn parse_list<T>(
+  value: &str,
+  parse_item: impl Fn(&str) -> Option<T>
+) -> Option<Vec<T>> {
+  value.split(',').map(parse_item).collect()
+}
+
+/// Reads UNTIL, which is UTC when it ends in `Z` and otherwise on the wall
+/// clock of DTSTART, through the end of the day when it is a date.
+fn parse_until(value: &str) -> Option<Until> {
+  if let Some(utc_value) = value.strip_suffix('Z') {
+    return Some(Until::Instant(parse_date_time(utc_value)?.to_zoned(tz::TimeZone::UTC).ok()?.timestamp()));
+  }
+
+  match value.len() {
+    8 => Some(Until::DateTime(parse_date(value)?.to_datetime(civil::Time::MAX))),
+    _ => Some(Until::DateTime(parse_date_time(value)?))
+  }
+}
+
+fn parse_rule(
+  rule: &str,
+  line_number: usize
+) -> Result<RecurrenceRule, IcsError> {
+  let unsupported = || IcsError::UnsupportedRule { line_number, rule: rule.to_string() };
+  let mut recurrence_rule = RecurrenceRule {
+    frequency: Frequency::Daily,
+    interval: 1,
+    count: None,
+    until: None,
+    by_month: vec![],
+    by_month_day: vec![],
+    by_day: vec![],
+    week_start: civil::Weekday::Monday
+  };
+  let mut frequency = None;
+
+  for part in rule.split(';') {
+    let (name, value) = part.split_once('=').ok_or_else(unsupported)?;
+    let parsed = match name.to_ascii_uppercase().as_str() {
+      "FREQ" => {
+        frequency = match value.to_ascii_uppercase().as_str() {
+          "DAILY" => Some(Frequency::Daily),
+          "WEEKLY" => Some(Frequency::Weekly),
+          "MONTHLY" => Some(Frequency::Monthly),
+          "YEARLY" => Some(Frequency::Yearly),
+          _ => None
+        };
+
+        frequency.map(|_| ())
+      },
+      "INTERVAL" => value.parse().ok().filter(|&interval| interval > 0).map(|interval| recurrence_rule.interval = interval),
+      "COUNT" => value.parse().ok().map(|count| recurrence_rule.count = Some(count)),
+      "UNTIL" => parse_until(value).map(|until| recurrence_rule.until = Some(until)),
+      "BYMONTH" => parse_list(value, |month| {
+        month.parse().ok().filter(|month| (1 ..= 12).contains(month))
+      }).map(|by_month| recurrence_rule.by_month = by_month),
+      "BYMONTHDAY" => parse_list(value, |day| {
+        day.parse().ok().filter(|day: &i8| *day != 0 && (-31 ..= 31).contains(day))
+      }).map(|by_month_day| recurrence_rule.by_month_day = by_month_day),
+      "BYDAY" => parse_list(value, parse_nth_weekday).map(|by_day| recurrence_rule.by_day = by_day),
+      "WKST" => parse_weekday(value).map(|week_start| recurrence_rule.week_start = week_start),
+      _ => None
+    };
+
+    parsed.ok_or_else(unsupported)?;
+  }
+
+  recurrence_rule.frequency = frequency.ok_or_else(unsupported)?;
+
+  // Weeks of the month only count within a month, and the other BYDAY and
+  // BYMONTHDAY combinations expand by weeks of the year, which are not read.
+  let has_nth_weekday = recurrence_rule.by_day.iter().any(|(week, _)| week.is_some());
+  let is_supported = match recurrence_rule.frequency {
+    Frequency::Daily => !has_nth_weekday,
+    Frequency::Weekly => !has_nth_weekday && recurrence_rule.by_month_day.is_empty(),
+    Frequency::Monthly => true,
+    Frequency::Yearly => recurrence_rule.by_day.is_empty() || !recurrence_rule.by_month.is_empty()
+  };
+
+  if !is_supported {
+    return Err(unsupported());
+  }
+
+  Ok(recurrence_rule)
+}
+
+impl RecurrenceRule {
+  /// Whether `date` passes every BYMONTH, BYMONTHDAY and BYDAY filter.
+  fn matches(
+    &self,
+    date: civil::Date
+  ) -> bool {
+    let day = date.day();
+    let days_in_month = date.days_in_month();
+    let week_of_month = |week: i8| if week > 0 {
+      (day - 1) / 7 + 1 == week
+    } else {
+      (days_in_month - day) / 7 + 1 == -week
+    };
+
+    (self.by_month.is_empty() || self.by_month.contains(&date.month()))
+      && (self.by_month_day.is_empty() || self.by_month_day.iter().any(|&month_day| {
+        month_day == day || month_day == day - days_in_month - 1
+      }))
+      && (self.by_day.is_empty() || self.by_day.iter().any(|&(week, weekday)| {
+        weekday == date.weekday() && week.is_none_or(week_of_month)
+      }))
+  }
+
+  /// The dates in a month that may start an occurrence: every day when the
+  /// rule picks them by BYMONTHDAY or BYDAY, or else the day DTSTART is on.
+  fn get_month_dates(
+    &self,
+    first_of_month: civil::Date,
+    start_day: i8
+  ) -> Vec<civil::Date> {
+    if self.by_month_day.is_empty() && self.by_day.is_empty() {
+      return first_of_month.with().day(start_day).build().into_iter().collect();
+    }
+
+    (0 .. first_of_month.days_in_month()).filter_map(|day| first_of_month.checked_add(day.days()).ok()).collect()
+  }
+
+  /// The first day of the `period`th period after the one `start` is in,
+  /// with the dates in it that start an occurrence, in order.
+  fn get_period_dates(
+    &self,
+    start: civil::Date,
+    period: i64
+  ) -> Option<(civil::Date, Vec<civil::Date>)> {
+    let steps = period.checked_mul(self.interval)?;
+    let (period_start, mut dates) = match self.frequency {
+      Frequency::Daily => {
+        let day = start.checked_add(steps.days()).ok()?;
+
+        (day, vec![day])
+      },
+      Frequency::Weekly => {
+        let first_of_week = start.checked_sub((start.weekday().since(self.week_start) as i64).days()).ok()?
+          .checked_add(steps.checked_mul(7)?.days()).ok()?;
+        let weekdays: Vec<civil::Weekday> = match self.by_day.is_empty() {
+          true => vec![start.weekday()],
+          false => self.by_day.iter().map(|&(_, weekday)| weekday).collect()
+        };
+
+        (first_of_week, weekdays.iter().filter_map(|weekday| {
+          first_of_week.checked_add((weekday.since(self.week_start) as i64).days()).ok()
+        }).collect())
+      },
+      Frequency::Monthly => {
+        let first_of_month = start.first_of_month().checked_add(steps.months()).ok()?;
+
+        (first_of_month, self.get_month_dates(first_of_month, start.day()))
+      },
+      Frequency::Yearly => {
+        let first_of_year = start.first_of_year().checked_add(steps.years()).ok()?;
+        let months = match self.by_month.is_empty() {
+          true => vec![start.month()],
+          false => self.by_month.clone()
+        };
+
+        (first_of_year, months.iter().filter_map(|&month| first_of_year.with().month(month).build().ok()).flat_map(|first_of_month| {
+          self.get_month_dates(first_of_month, start.day())
+        }).collect())
+      }
+    };
+
+    dates.retain(|&date| self.matches(date));
+    dates.sort();
+    dates.dedup();
+
+    Some((period_start, dates))
+  }
+}
+
+/// Reads every VEVENT out of iCalendar text, in the order they appear, along
+/// with the VTIMEZONE for each TZID they use. Components nested in events,
+/// such as alarms, are skipped. Recurrences are kept as the file gives them,
+/// for `get_events_in_range` to expand.
+pub fn parse_ics(text: &str) -> Result<Vec<IcsEvent>, IcsError> {
+  let mut ics_events: Vec<IcsEvent> = vec![];
+  // Components that are open, with the line each one begins on.
+  let mut components: Vec<(usize, String)> = vec![];
+  let mut event_lines: Vec<ContentLine> = vec![];
+  let mut time_zone_name: Option<String> = None;
+  let mut observances: Vec<Option<Observance>> = vec![];
+  let mut observance_lines: Vec<ContentLine> = vec![];
+  let mut time_zones: Vec<(String, tz::TimeZone)> = vec![];
+
+  for (line_number, line) in unfold_lines(text) {
+    let content_line = parse_content_line(line_number, &line)?;
+    let component_name = content_line.value.trim().to_ascii_uppercase();
+
+    match content_line.name.as_str() {
+      "BEGIN" => components.push((line_number, component_name)),
+      "END" => {
+        let (begin_line_number, name) = match components.pop() {
+          Some(component) if component.1 == component_name => component,
+          _ => return Err(IcsError::InvalidLine { line_number })
+        };
+
+        match name.as_str() {
+          "VEVENT" => {
+            ics_events.push(parse_event(begin_line_number, &event_lines)?);
+            event_lines.clear();
+          },
+          "STANDARD" | "DAYLIGHT" => {
+            if components.last().is_some_and(|(_, name)| name == "VTIMEZONE") {
+              observances.push(parse_observance(name == "DAYLIGHT", &observance_lines));
+            }
+
+            observance_lines.clear();
+          },
+          "VTIMEZONE" => {
+            // A VTIMEZONE with an observance that does not parse is left out,
+            // so events in it report an unknown time zone.
+            let vtimezone = observances.drain(..).collect::<Option<Vec<Observance>>>().as_deref().and_then(get_vtimezone);
+
+            if let (Some(name), Some(vtimezone)) = (time_zone_name.take(), vtimezone) {
+              time_zones.push((name, vtimezone));
+            }
+          },
+          _ => {}
+        }
+      },
+      _ => match components.last().map(|(_, name)| name.as_str()) {
+        Some("VEVENT") => event_lines.push(content_line),
+        Some("VTIMEZONE") if content_line.name == "TZID" => time_zone_name = Some(content_line.value.trim().to_string()),
+        Some("STANDARD") | Some("DAYLIGHT") => observance_lines.push(content_line),
+        _ => {}
+      }
+    }
+  }
+
+  if let Some((line_number, name)) = components.pop() {
+    return Err(IcsError::UnclosedComponent { line_number, name });
+  }
+
+  for ics_event in ics_events.iter_mut() {
+    let IcsEvent { start, end, extra_starts, excluded_starts, recurrence_id, .. } = ics_event;
+
+    for ics_time in std::iter::once(start).chain(end).chain(extra_starts).chain(excluded_starts).chain(recurrence_id) {
+      set_vtimezone(ics_time, &time_zones);
+    }
+  }
+
+  Ok(ics_events)
+}
+
+impl IcsTime {
+  /// Places the time in `time_zone` unless it names its own, which the tz
+  /// database resolves ahead of the file's VTIMEZONE. Dates resolve to the
+  /// midnight starting them.
+  pub fn to_zoned(
+    &self,
+    time_zone: &tz::TimeZone,
+    line_number: usize
+  ) -> Result<Zoned, IcsError> {
+    let out_of_range = |_| IcsError::OutOfRange { line_number };
+
+    match self {
+      IcsTime::Date(date) => date.to_zoned(time_zone.clone()).map_err(out_of_range),
+      IcsTime::Floating(date_time) => date_time.to_zoned(time_zone.clone()).map_err(out_of_range),
+      IcsTime::Utc(date_time) => date_time.to_zoned(tz::TimeZone::UTC).map_err(out_of_range),
+      IcsTime::Zoned { date_time, time_zone_name, time_zone: vtimezone } => {
+        let own_time_zone = match (tz::TimeZone::get(time_zone_name), vtimezone) {
+          (Ok(own_time_zone), _) => own_time_zone,
+          (Err(_), Some(vtimezone)) => vtimezone.clone(),
+          (Err(_), None) => return Err(IcsError::UnknownTimeZone { line_number, name: time_zone_name.clone() })
+        };
+
+        date_time.to_zoned(own_time_zone).map_err(out_of_range)
+      }
+    }
+  }
+
+  fn get_date_time(&self) -> civil::DateTime {
+    match self {
+      IcsTime::Date(date) => date.to_datetime(civil::Time::midnight()),
+      IcsTime::Floating(date_time) | IcsTime::Utc(date_time) | IcsTime::Zoned { date_time, .. } => *date_time
+    }
+  }
+
+  /// The same kind of time, in the same time zone, at `date_time`.
+  fn with_date_time(
+    &self,
+    date_time: civil::DateTime
+  ) -> IcsTime {
+    match self {
+      IcsTime::Date(_) => IcsTime::Date(date_time.date()),
+      IcsTime::Floating(_) => IcsTime::Floating(date_time),
+      IcsTime::Utc(_) => IcsTime::Utc(date_time),
+      IcsTime::Zoned { time_zone_name, time_zone, .. } => IcsTime::Zoned {
+        date_time,
+        time_zone_name: time_zone_name.clone(),
+        time_zone: time_zone.clone()
+      }
+    }
+  }
+}
+
+/// Starts of the occurrences `rule` adds after `start`, up to `last_date`.
+fn get_rule_starts(
+  rule: &RecurrenceRule,
+  start: &IcsTime,
+  last_date: civil::Date,
+  time_zone: &tz::TimeZone,
+  line_number: usize
+) -> Result<Vec<IcsTime>, IcsError> {
+  let start_date_time = start.get_date_time();
+  let mut starts: Vec<IcsTime> = vec![];
+  // DTSTART is always the first occurrence.
+  let mut count = 1;
+
+  for period in 0 .. {
+    let dates = match rule.get_period_dates(start_date_time.date(), period) {
+      Some((period_start, dates)) if period_start <= last_date => dates,
+      _ => break
+    };
+
+    for date in dates {
+      let date_time = date.to_datetime(start_date_time.time());
+
+      if date_time <= start_date_time {
+        continue;
+      }
+
+      if date > last_date || rule.count.is_some_and(|rule_count| count >= rule_count) {
+        return Ok(starts);
+      }
+
+      let occurrence_start = start.with_date_time(date_time);
+      let is_after_until = match &rule.until {
+        Some(Until::DateTime(until)) => date_time > *until,
+        Some(Until::Instant(until)) => occurrence_start.to_zoned(time_zone, line_number)?.timestamp() > *until,
+        None => false
+      };
+
+      if is_after_until {
+        return Ok(starts);
+      }
+
+      starts.push(occurrence_start);
+      count += 1;
+    }
+  }
+
+  Ok(starts)
+}
+
+impl IcsEvent {
+  /// Resolves the occurrences of the event that start before `range_end`,
+  /// as `to_event` does, from DTSTART, RRULE and RDATE less EXDATE, in order.
+  /// Each occurrence lasts as long as the first, in days for all-day events
+  /// and in elapsed time otherwise.
+  pub fn to_events(
+    &self,
+    range_end: f64,
+    time_zone: &tz::TimeZone
+  ) -> Result<Vec<event::Event>, IcsError> {
+    let out_of_range = |_| IcsError::OutOfRange { line_number: self.line_number };
+    let first = self.to_event(time_zone)?;
+    let length = match (&self.start, &self.end) {
+      (IcsTime::Date(start), Some(IcsTime::Date(end))) => Some(start.until(*end).map_err(out_of_range)?),
+      (_, Some(_)) => Some(((first.end - first.start) as i64).seconds()),
+      (_, None) => self.duration
+    };
+    let mut starts = self.extra_starts.clone();
+
+    if let Some(rule) = &self.rule {
+      // Offsets stay within a day or so of UTC, so two days past the UTC date
+      // of `range_end` takes every occurrence that starts before it.
+      let last_date = Timestamp::from_second(range_end as i64).map_err(out_of_range)?
+        .to_zoned(tz::TimeZone::UTC)
+        .date()
+        .checked_add(2.days())
+        .map_err(out_of_range)?;
+
+      starts.extend(get_rule_starts(&parse_rule(rule, self.line_number)?, &self.start, last_date, time_zone, self.line_number)?);
+    }
+
+    let excluded_starts = self.excluded_starts.iter()
+      .map(|excluded_start| Ok(excluded_start.to_zoned(time_zone, self.line_number)?.timestamp().as_second() as f64))
+      .collect::<Result<Vec<f64>, IcsError>>()?;
+    let mut events = vec![first];
+
+    for start in starts.iter() {
+      let start = start.to_zoned(time_zone, self.line_number)?;
+      let end = match length {
+        Some(length) => start.checked_add(length).map_err(out_of_range)?,
+        None if first.all_day => start.tomorrow().map_err(out_of_range)?,
+        None => start.clone()
+      };
+
+      events.push(event::Event {
+        start: start.timestamp().as_second() as f64,
+        end: end.timestamp().as_second() as f64,
+        all_day: first.all_day
+      });
+    }
+
+    events.retain(|event| !excluded_starts.contains(&event.start) && event.start < range_end);
+    events.sort_by(|a, b| a.start.total_cmp(&b.start));
+    events.dedup_by(|a, b| a.start == b.start);
+
+    Ok(events)
+  }
+
+  /// Resolves the event to instants, in seconds since the Unix epoch, reading
+  /// floating times and dates in `time_zone`. With neither DTEND nor DURATION,
+  /// events on a date last the day and other events last no time at all.
+  pub fn to_event(
+    &self,
+    time_zone: &tz::TimeZone
+  ) -> Result<event::Event, IcsError> {
+    let out_of_range = |_| IcsError::OutOfRange { line_number: self.line_number };
+    let start = self.start.to_zoned(time_zone, self.line_number)?;
+    let all_day = matches!(self.start, IcsTime::Date(_));
+    let end = match (&self.end, self.duration) {
+      (Some(end), _) => end.to_zoned(time_zone, self.line_number)?,
+      (None, Some(duration)) => start.checked_add(duration).map_err(out_of_range)?,
+      (None, None) if all_day => start.tomorrow().map_err(out_of_range)?,
+      (None, None) => start.clone()
+    };
+
+    Ok(event::Event {
+      start: start.timestamp().as_second() as f64,
+      end: end.timestamp().as_second() as f64,
+      all_day
+    })
+  }
+}
+
+/// Events resolved for a range, each paired with its index in the
+/// `IcsEvent`s they came from, along with the events that could not be.
+#[derive(Clone, Debug, Default)]
+pub struct EventsInRange {
+  pub events: Vec<(usize, event::Event)>,
+  pub skipped: Vec<(usize, IcsError)>,
+}
+
+/// Resolves `ics_events` with `IcsEvent::to_events` and keeps the
+/// occurrences covering any of `range_start .. range_end`, less those an
+/// event with the same UID replaces through its RECURRENCE-ID. An event that
+/// fails to resolve is skipped, so one bad event does not hide the rest.
+pub fn get_events_in_range(
+  ics_events: &[IcsEvent],
+  range_start: f64,
+  range_end: f64,
+  time_zone: &tz::TimeZone
+) -> EventsInRange {
+  let mut events_in_range = EventsInRange::default();
+  let replaced_starts: Vec<(&str, f64)> = ics_events.iter().filter_map(|ics_event| {
+    let recurrence_id = ics_event.recurrence_id.as_ref()?.to_zoned(time_zone, ics_event.line_number).ok()?;
+
+    Some((ics_event.uid.as_deref()?, recurrence_id.timestamp().as_second() as f64))
+  }).collect();
+
+  for (i, ics_event) in ics_events.iter().enumerate() {
+    let is_replaced = |event: &event::Event| ics_event.recurrence_id.is_none() && replaced_starts.iter().any(|&(uid, start)| {
+      ics_event.uid.as_deref() == Some(uid) && event.start == start
+    });
+
+    match ics_event.to_events(range_end, time_zone) {
+      Ok(events) => events_in_range.events.extend(events.into_iter().filter(|event| {
+        week_tiler::covers_range(event, range_start, range_end) && !is_replaced(event)
+      }).map(|event| (i, event))),
+      Err(error) => events_in_range.skipped.push((i, error))
+    }
+  }
+
+  events_in_range
+}
+
+/// Places the parts of `ics_events` that fall on `day` on it, ready for
+/// `json::tile_json_events`. Each event is keyed by its UID and carries its
+/// SUMMARY in the payload. Events skipped by `get_events_in_range` are
+/// returned alongside.
+pub fn get_json_events(
+  ics_events: &[IcsEvent],
+  day: &time::DisplayDay
+) -> (Vec<json::JsonEvent>, Vec<(usize, IcsError)>) {
+  let events_in_range = get_events_in_range(ics_events, day.start, day.end, day.get_time_zone());
+  let events: Vec<event::Event> = events_in_range.events.iter().map(|(_, event)| *event).collect();
+
+  let json_events = day.localize_events(&events).into_iter().map(|segment| {
+    let ics_event = &ics_events[events_in_range.events[segment.original_index].0];
+
+    json::JsonEvent {
+      id: ics_event.uid.clone().map(Value::from),
+      event: segment.event,
+      payload: ics_event.summary.as_ref().map(|summary| json!({ "summary": summary }))
+    }
+  }).collect();
+
+  (json_events, events_in_range.skipped)
+}
@@ -0,0 +1,76 @@
+use crate::sorted_event;
+use crate::tile;
+use crate::tiling_method;
+use crate::tiler_options;
+use crate::tiler_error;
+use crate::overlap_cluster;
+
+// Places every event in the leftmost column holding no event it overlaps. The
+// earlier events an event overlaps all overlap each other too, so they never
+// fill more than the cluster's maximum concurrency less one columns, and the
+// coloring is optimal. Events that only touch, within `error_bound`, may share
+// a column.
+fn get_column_indices(
+  cluster: &[sorted_event::SortedEvent],
+  tiler_options: &tiler_options::TilerOptions
+) -> (Vec<usize>, usize) {
+  let mut column_indices: Vec<usize> = vec![0; cluster.len()];
+  let mut number_of_columns: usize = 0;
+  let mut in_progress: Vec<usize> = vec![];
+
+  for (i, sorted_event) in cluster.iter().enumerate() {
+    in_progress.retain(|&j| cluster[j].event.end - tiler_options.error_bound > sorted_event.event.start);
+
+    let mut is_column_taken: Vec<bool> = vec![false; number_of_columns];
+
+    for &j in in_progress.iter() {
+      if cluster[j].event.overlaps(&sorted_event.event, tiler_options.error_bound) {
+        is_column_taken[column_indices[j]] = true;
+      }
+    }
+
+    column_indices[i] = is_column_taken.iter().position(|&is_taken| !is_taken).unwrap_or(number_of_columns);
+    number_of_columns = number_of_columns.max(column_indices[i] + 1);
+    in_progress.push(i);
+  }
+
+  (column_indices, number_of_columns)
+}
+
+fn tile_cluster(
+  cluster: &[sorted_event::SortedEvent],
+  tiles: &mut [tile::Tile],
+  tiler_options: &tiler_options::TilerOptions
+) {
+  let (column_indices, number_of_columns) = get_column_indices(cluster, tiler_options);
+  let column_dx = 1.0 / (number_of_columns as f64);
+
+  for (sorted_event, &column_index) in cluster.iter().zip(column_indices.iter()) {
+    tiles[sorted_event.sorted_index].x = (column_index as f64) * column_dx;
+    tiles[sorted_event.sorted_index].dx = column_dx;
+  }
+}
+
+#[derive(Default)]
+pub struct IntervalColoringTilingMethod;
+
+impl IntervalColoringTilingMethod {
+  pub fn new() -> IntervalColoringTilingMethod {
+    IntervalColoringTilingMethod {}
+  }
+}
+
+impl tiling_method::TilingMethod for IntervalColoringTilingMethod {
+  fn tile(
+    &self,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
+    tiler_options: &tiler_options::TilerOptions
+  ) -> Result<(), tiler_error::TilerError> {
+    for range in overlap_cluster::get_cluster_ranges(sorted_events, tiler_options) {
+      tile_cluster(&sorted_events[range], tiles, tiler_options);
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::event;
+use crate::tile;
+use crate::tiler;
+use crate::tiler_error;
+use crate::tiler_options;
+use crate::tiling_method;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsonEvent {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub id: Option<Value>,
+  #[serde(flatten)]
+  pub event: event::Event,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub payload: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JsonTile {
+  pub id: Value,
+  #[serde(flatten)]
+  pub tile: tile::Tile,
+  /// Lane of an all-day event, counted from the top.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub all_day_lane: Option<usize>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub payload: Option<Value>,
+}
+
+/// Tiles `json_events`, returning one tile per event in the original order.
+/// Events without an `id` are keyed by their index in `json_events`, and
+/// all-day events carry their lane.
+pub fn tile_json_events<T: tiling_method::TilingMethod>(
+  json_events: Vec<JsonEvent>,
+  tiler_options: tiler_options::TilerOptions,
+  tiling_method: T
+) -> Result<Vec<JsonTile>, tiler_error::TilerError> {
+  let events: Vec<event::Event> = json_events.iter().map(|e| e.event).collect();
+  let mut tiler = tiler::Tiler::new(events, tiler_options, tiling_method)?;
+
+  tiler.tile()?;
+
+  let mut all_day_lanes: Vec<Option<usize>> = vec![None; json_events.len()];
+
+  for all_day_tile in tiler.all_day_tiles.iter() {
+    all_day_lanes[all_day_tile.original_index] = Some(all_day_tile.lane);
+  }
+
+  Ok(tiler.get_tiles_in_original_order()
+          .into_iter()
+          .zip(json_events)
+          .zip(all_day_lanes)
+          .enumerate()
+          .map(|(i, ((tile, json_event), all_day_lane))| JsonTile {
+            id: json_event.id.unwrap_or_else(|| Value::from(i)),
+            tile,
+            all_day_lane,
+            payload: json_event.payload
+          })
+          .collect())
+}
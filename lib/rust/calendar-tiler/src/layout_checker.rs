@@ -0,0 +1,202 @@
+use std::fmt::{self, Formatter, Display};
+use crate::event;
+use crate::tile;
+use crate::tiler_options;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum LayoutViolation {
+  TileCountMismatch {
+    number_of_tiles: usize,
+    number_of_events: usize,
+  },
+  NonFiniteTile {
+    index: usize,
+    x: f64,
+    dx: f64,
+  },
+  NegativeX {
+    index: usize,
+    x: f64,
+  },
+  NonPositiveDx {
+    index: usize,
+    dx: f64,
+  },
+  OverflowingDx {
+    index: usize,
+    x: f64,
+    dx: f64,
+  },
+  TimeMismatch {
+    index: usize,
+    tile: tile::Tile,
+    event: event::Event,
+  },
+  HorizontalOverlap {
+    index: usize,
+    other_index: usize,
+  },
+}
+
+impl Display for LayoutViolation {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    match self {
+      LayoutViolation::TileCountMismatch { number_of_tiles, number_of_events } => write!(
+        f,
+        "{} tiles were laid out for {} events",
+        number_of_tiles,
+        number_of_events
+      ),
+      LayoutViolation::NonFiniteTile { index, x, dx } => write!(
+        f,
+        "tile {} has a non-finite position x = {} dx = {}",
+        index,
+        x,
+        dx
+      ),
+      LayoutViolation::NegativeX { index, x } => write!(
+        f,
+        "tile {} starts left of the day at x = {}",
+        index,
+        x
+      ),
+      LayoutViolation::NonPositiveDx { index, dx } => write!(
+        f,
+        "tile {} has a non-positive width dx = {}",
+        index,
+        dx
+      ),
+      LayoutViolation::OverflowingDx { index, x, dx } => write!(
+        f,
+        "tile {} ends right of the day at x + dx = {}",
+        index,
+        x + dx
+      ),
+      LayoutViolation::TimeMismatch { index, tile, event } => write!(
+        f,
+        "tile {} spans y = {} dy = {} but its event spans {} to {}",
+        index,
+        tile.y,
+        tile.dy,
+        event.start,
+        event.end
+      ),
+      LayoutViolation::HorizontalOverlap { index, other_index } => write!(
+        f,
+        "tiles {} and {} overlap in time and horizontally",
+        index,
+        other_index
+      ),
+    }
+  }
+}
+
+fn check_tile(
+  index: usize,
+  tile: &tile::Tile,
+  event: &event::Event,
+  error_bound: f64,
+  violations: &mut Vec<LayoutViolation>
+) {
+  // NaN fails every comparison below, so it has to be caught on its own.
+  if !tile.x.is_finite() || !tile.dx.is_finite() {
+    violations.push(LayoutViolation::NonFiniteTile { index, x: tile.x, dx: tile.dx });
+  } else {
+    if tile.x < -error_bound {
+      violations.push(LayoutViolation::NegativeX { index, x: tile.x });
+    }
+
+    if tile.dx <= 0.0 {
+      violations.push(LayoutViolation::NonPositiveDx { index, dx: tile.dx });
+    }
+
+    if tile.x + tile.dx > 1.0 + error_bound {
+      violations.push(LayoutViolation::OverflowingDx { index, x: tile.x, dx: tile.dx });
+    }
+  }
+
+  if (tile.y - event.start).abs() > error_bound
+    || (tile.dy - (event.end - event.start)).abs() > error_bound {
+    violations.push(LayoutViolation::TimeMismatch { index, tile: *tile, event: *event });
+  }
+}
+
+/// Checks a finished layout, where `tiles[i]` is the tile of `events[i]`, and
+/// returns every violated invariant. An empty result means the layout is valid.
+/// Tiles stacked at different `z` may overlap, and so may the tiles of all-day
+/// events, which are drawn in their own lanes.
+pub fn check_layout(
+  tiles: &[tile::Tile],
+  events: &[event::Event],
+  tiler_options: &tiler_options::TilerOptions
+) -> Vec<LayoutViolation> {
+  let error_bound = tiler_options.error_bound;
+  let mut violations: Vec<LayoutViolation> = vec![];
+
+  if tiles.len() != events.len() {
+    violations.push(LayoutViolation::TileCountMismatch {
+      number_of_tiles: tiles.len(),
+      number_of_events: events.len()
+    });
+
+    return violations;
+  }
+
+  for (index, (tile, event)) in tiles.iter().zip(events.iter()).enumerate() {
+    check_tile(index, tile, event, error_bound, &mut violations);
+  }
+
+  let mut indices: Vec<usize> = (0 .. events.len()).collect();
+
+  indices.sort_by(|a, b| events[*a].start.total_cmp(&events[*b].start));
+
+  for (i, &index) in indices.iter().enumerate() {
+    for &other_index in indices.iter().skip(i + 1) {
+      if events[other_index].start >= events[index].end - error_bound {
+        break;
+      }
+
+      if events[index].all_day
+        || events[other_index].all_day
+        || !events[index].overlaps(&events[other_index], error_bound) {
+        continue;
+      }
+
+      let tile = &tiles[index];
+      let other_tile = &tiles[other_index];
+
+      if tile.z == other_tile.z
+        && tile.x < other_tile.x + other_tile.dx - error_bound
+        && other_tile.x < tile.x + tile.dx - error_bound {
+        violations.push(LayoutViolation::HorizontalOverlap {
+          index: index.min(other_index),
+          other_index: index.max(other_index)
+        });
+      }
+    }
+  }
+
+  violations
+}
+
+/// Panics with every violation of `check_layout` when debug assertions are
+/// enabled, and does nothing otherwise.
+#[track_caller]
+pub fn debug_assert_layout(
+  tiles: &[tile::Tile],
+  events: &[event::Event],
+  tiler_options: &tiler_options::TilerOptions
+) {
+  if cfg!(debug_assertions) {
+    let violations = check_layout(tiles, events, tiler_options);
+
+    if !violations.is_empty() {
+      let messages: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+
+      panic!("invalid layout:\n{}", messages.join("\n"));
+    }
+  }
+}
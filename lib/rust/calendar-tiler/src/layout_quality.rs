@@ -0,0 +1,198 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Formatter, Display};
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+use crate::event;
+use crate::tile;
+use crate::tiler;
+use crate::tiler_options;
+use crate::overlap_cluster;
+
+/// The measure of a layout that `compare` ranks by first.
+#[wasm_bindgen]
+#[derive(PartialEq, Copy, Clone, Debug, Default)]
+pub enum LayoutObjective {
+  #[default]
+  MinWidth = 1,
+  AreaUtilization = 2,
+  WidthVariance = 3,
+  NarrowTileCount = 4,
+}
+
+impl LayoutObjective {
+  pub const ALL: [LayoutObjective; 4] = [
+    LayoutObjective::MinWidth,
+    LayoutObjective::AreaUtilization,
+    LayoutObjective::WidthVariance,
+    LayoutObjective::NarrowTileCount
+  ];
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      LayoutObjective::MinWidth => "min-width",
+      LayoutObjective::AreaUtilization => "area-utilization",
+      LayoutObjective::WidthVariance => "width-variance",
+      LayoutObjective::NarrowTileCount => "narrow-tile-count"
+    }
+  }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct ParseLayoutObjectiveError(pub String);
+
+impl Display for ParseLayoutObjectiveError {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    write!(f, "unknown layout objective: {}", self.0)
+  }
+}
+
+impl std::error::Error for ParseLayoutObjectiveError {}
+
+impl Display for LayoutObjective {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+impl FromStr for LayoutObjective {
+  type Err = ParseLayoutObjectiveError;
+
+  fn from_str(
+    s: &str
+  ) -> Result<LayoutObjective, Self::Err> {
+    LayoutObjective::ALL.iter()
+                        .find(|objective| objective.name() == s)
+                        .copied()
+                        .ok_or_else(|| ParseLayoutObjectiveError(s.to_string()))
+  }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct LayoutQuality {
+  /// Width of the narrowest tile.
+  pub min_width: f64,
+  /// Share of every overlap cluster's span, across the full width, that
+  /// tiles cover.
+  pub area_utilization: f64,
+  /// Variance of tile widths around the mean width of their overlap cluster.
+  pub width_variance: f64,
+  /// Number of tiles narrower than `TilerOptions::readable_width`.
+  pub narrow_tile_count: usize,
+}
+
+impl LayoutQuality {
+  // Every measure turned so that larger is better, led by the objective and
+  // then in the order of `LayoutObjective` to break ties.
+  fn get_ranked_measures(
+    &self,
+    objective: LayoutObjective
+  ) -> [f64; 4] {
+    let measures = [
+      (LayoutObjective::MinWidth, self.min_width),
+      (LayoutObjective::AreaUtilization, self.area_utilization),
+      (LayoutObjective::WidthVariance, -self.width_variance),
+      (LayoutObjective::NarrowTileCount, -(self.narrow_tile_count as f64)),
+    ];
+    let mut ranked_measures = [0.0; 4];
+    let mut i = 1;
+
+    for &(measure_objective, measure) in measures.iter() {
+      if measure_objective == objective {
+        ranked_measures[0] = measure;
+      } else {
+        ranked_measures[i] = measure;
+        i += 1;
+      }
+    }
+
+    ranked_measures
+  }
+
+  /// Orders two layouts so that the better one by `objective` is greater.
+  /// Measures within `error_bound` of each other count as equal.
+  pub fn compare(
+    &self,
+    other: &LayoutQuality,
+    objective: LayoutObjective,
+    error_bound: f64
+  ) -> Ordering {
+    let measures = self.get_ranked_measures(objective);
+    let other_measures = other.get_ranked_measures(objective);
+
+    for (measure, other_measure) in measures.iter().zip(other_measures.iter()) {
+      if (measure - other_measure).abs() > error_bound {
+        return measure.total_cmp(other_measure);
+      }
+    }
+
+    Ordering::Equal
+  }
+}
+
+impl Display for LayoutQuality {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    write!(
+      f,
+      "min width: {0}, area utilization: {1}, width variance: {2}, narrow tiles: {3}",
+      self.min_width,
+      self.area_utilization,
+      self.width_variance,
+      self.narrow_tile_count
+    )
+  }
+}
+
+/// Scores a finished layout, where `tiles[i]` is the tile of `events[i]`.
+/// All-day events are left out, and an empty day scores as a perfect one.
+pub fn score_layout(
+  tiles: &[tile::Tile],
+  events: &[event::Event],
+  tiler_options: &tiler_options::TilerOptions
+) -> LayoutQuality {
+  let mut sorted_events = tiler::sort_events(events, tiler_options);
+
+  sorted_events.retain(|sorted_event| !sorted_event.event.all_day);
+
+  let mut min_width: f64 = 1.0;
+  let mut covered_area: f64 = 0.0;
+  let mut cluster_area: f64 = 0.0;
+  let mut squared_deviations: f64 = 0.0;
+  let mut narrow_tile_count: usize = 0;
+
+  for range in overlap_cluster::get_cluster_ranges(&sorted_events, tiler_options) {
+    let cluster = &sorted_events[range];
+    let end = cluster.iter().map(|sorted_event| sorted_event.event.end).fold(f64::NEG_INFINITY, f64::max);
+    let total_width: f64 = cluster.iter().map(|sorted_event| tiles[sorted_event.original_index].dx).sum();
+    let mean_width = total_width / (cluster.len() as f64);
+
+    cluster_area += end - cluster[0].event.start;
+
+    for sorted_event in cluster.iter() {
+      let tile = &tiles[sorted_event.original_index];
+
+      min_width = min_width.min(tile.dx);
+      covered_area += tile.dx * tile.dy;
+      squared_deviations += (tile.dx - mean_width).powi(2);
+
+      if tile.dx < tiler_options.readable_width - tiler_options.error_bound {
+        narrow_tile_count += 1;
+      }
+    }
+  }
+
+  LayoutQuality {
+    min_width,
+    area_utilization: if cluster_area > 0.0 { covered_area / cluster_area } else { 1.0 },
+    width_variance: if sorted_events.is_empty() { 0.0 } else { squared_deviations / (sorted_events.len() as f64) },
+    narrow_tile_count,
+  }
+}
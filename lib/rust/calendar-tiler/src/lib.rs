@@ -1,19 +1,88 @@
+//! Lays out calendar events as tiles within a single day.
+//!
+//! ```
+//! use calendar_tiler::{Event, Tiler, TilerOptions, BasicTilingMethod};
+//!
+//! let mut tiler = Tiler::new(
+//!   vec![Event::new(9.0, 10.5), Event::new(10.0, 11.0)],
+//!   TilerOptions::new(0.0001),
+//!   BasicTilingMethod::new()
+//! )?;
+//!
+//! tiler.tile()?;
+//!
+//! let tiles = tiler.get_tiles_in_original_order();
+//!
+//! assert_eq!(tiles[1].y, 10.0);
+//! assert!(tiles[1].x > tiles[0].x);
+//! # Ok::<(), calendar_tiler::TilerError>(())
+//! ```
+
 mod utils;
 
-use wasm_bindgen::prelude::*;
+// Exposed so the benchmarks can time the DAG stage on its own.
+#[doc(hidden)]
+pub mod dag_builder;
+#[doc(hidden)]
+pub mod dag;
+
+pub mod event;
+pub mod tile;
+pub mod sorted_event;
+pub mod tiler_options;
+pub mod tiler_error;
+pub mod tiler;
+pub mod all_day_layout;
+pub mod week_tiler;
+pub mod month_layout;
+#[cfg(feature = "timezone")]
+pub mod time;
+pub mod tiling_method;
+pub mod basic_tiling_method;
+pub mod space_filling_tiling_method;
+pub mod time_respective_tiling_method;
+pub mod interval_coloring_tiling_method;
+pub mod expand_right_tiling_method;
+pub mod cascade_tiling_method;
+pub mod optimized_tiling_method;
+pub mod auto_tiling_method;
+pub mod simplex;
+pub mod layout_checker;
+pub mod layout_quality;
+pub mod overlap_cluster;
+pub mod svg;
+pub mod wasm;
+#[cfg(feature = "serde")]
+pub mod json;
+#[cfg(feature = "ics")]
+pub mod ics;
+
+pub use event::{Event, EventId};
+pub use tile::Tile;
+pub use sorted_event::SortedEvent;
+pub use tiler_options::TilerOptions;
+pub use tiler_error::{FallbackReason, InvalidEventReason, TilerError};
+pub use tiler::Tiler;
+pub use all_day_layout::{AllDayTile, DayLayout};
+pub use week_tiler::{DaySegment, WeekTile, WeekTiler};
+pub use month_layout::{layout_month, MonthBar, MonthLayout};
+#[cfg(feature = "timezone")]
+pub use time::{DisplayDay, TimeError};
+pub use layout_checker::{check_layout, LayoutViolation};
+pub use layout_quality::{score_layout, LayoutObjective, LayoutQuality, ParseLayoutObjectiveError};
+pub use overlap_cluster::{find_overlap_clusters, OverlapCluster};
+pub use tiling_method::{get_tiling_method, get_tiling_method_with_objective, ParseTilingMethodNameError, TilingMethod, TilingMethodName};
+pub use basic_tiling_method::BasicTilingMethod;
+pub use space_filling_tiling_method::SpaceFillingTilingMethod;
+pub use time_respective_tiling_method::TimeRespectiveTilingMethod;
+pub use interval_coloring_tiling_method::IntervalColoringTilingMethod;
+pub use expand_right_tiling_method::ExpandRightTilingMethod;
+pub use cascade_tiling_method::CascadeTilingMethod;
+pub use optimized_tiling_method::OptimizedTilingMethod;
+pub use auto_tiling_method::AutoTilingMethod;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
 #[cfg(feature = "wee_alloc")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
-
-#[wasm_bindgen]
-extern {
-    fn alert(s: &str);
-}
-
-#[wasm_bindgen]
-pub fn greet() {
-    alert("Hello, calendar-tiler!");
-}
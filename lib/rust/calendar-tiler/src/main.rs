@@ -1,64 +1,209 @@
-mod event;
-mod tile;
-mod sorted_event;
-mod tiler_options;
-mod tiler;
-mod tiling_method;
-mod basic_tiling_method;
-mod space_filling_tiling_method;
-mod time_respective_tiling_method;
-mod dag_builder;
-mod dag;
-use rand::Rng;
+mod cli;
+
+#[cfg(feature = "ics")]
+use calendar_tiler::ics;
+use calendar_tiler::json::{self, JsonEvent, JsonTile};
+use calendar_tiler::svg::{self, SvgOptions};
+#[cfg(feature = "ics")]
+use calendar_tiler::DisplayDay;
+use calendar_tiler::{get_tiling_method_with_objective, Event, Tile, TilerOptions, TilingMethodName};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
 use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::process;
 
 const HOURS_PER_DAY: u8 = 24;
 const MAX_NUMBER_OF_EVENTS: u8 = 60;
+const STDIN_ARG: &str = "-";
+const ICS_HEADER: &str = "BEGIN:VCALENDAR";
+
+// The day iCalendar input was tiled on, which never exists without iCalendar support.
+#[cfg(feature = "ics")]
+type IcsDay = DisplayDay;
+#[cfg(not(feature = "ics"))]
+enum IcsDay {}
+
+fn generate_random_events(seed: Option<u64>, count: Option<usize>) -> Vec<JsonEvent> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy()
+    };
+
+    let number_of_events = count.unwrap_or_else(|| rng.gen_range(1..MAX_NUMBER_OF_EVENTS as usize));
+    let mut events: Vec<JsonEvent> = vec![];
+
+    for _ in 0 .. number_of_events {
+        let start: f64 = rng.gen_range(0_f64..HOURS_PER_DAY as f64);
+        let end: f64 = rng.gen_range(start..(HOURS_PER_DAY as f64));
+
+        events.push(JsonEvent {
+            id: None,
+            event: Event::new(start, end),
+            payload: None
+        });
+    }
+
+    events
+}
+
+#[cfg(feature = "ics")]
+fn read_ics_events(text: &str, options: &cli::Options) -> Result<(Vec<JsonEvent>, IcsDay), Box<dyn Error>> {
+    let date = options.date.as_deref().ok_or("iCalendar input needs a --date to tile")?;
+    let day = DisplayDay::new(date, &options.time_zone)?;
+
+    let (json_events, skipped) = ics::get_json_events(&ics::parse_ics(text)?, &day);
+
+    for (_, error) in skipped.iter() {
+        eprintln!("warning: skipped an event: {}", error);
+    }
+
+    Ok((json_events, day))
+}
+
+#[cfg(not(feature = "ics"))]
+fn read_ics_events(_text: &str, _options: &cli::Options) -> Result<(Vec<JsonEvent>, IcsDay), Box<dyn Error>> {
+    Err("reading iCalendar input requires building with `--features ics`".into())
+}
+
+fn read_events(input: &str, options: &cli::Options) -> Result<(Vec<JsonEvent>, Option<IcsDay>), Box<dyn Error>> {
+    let reader: Box<dyn Read> = if input == STDIN_ARG {
+        Box::new(io::stdin())
+    } else {
+        Box::new(File::open(input)?)
+    };
+    let mut text = String::new();
+
+    BufReader::new(reader).read_to_string(&mut text)?;
+
+    if text.trim_start().get(.. ICS_HEADER.len()).is_some_and(|header| header.eq_ignore_ascii_case(ICS_HEADER)) {
+        let (json_events, day) = read_ics_events(&text, options)?;
+
+        Ok((json_events, Some(day)))
+    } else {
+        Ok((serde_json::from_str(&text)?, None))
+    }
+}
+
+// Events read from iCalendar carry their SUMMARY, which reads better than a UID.
+fn get_label(json_tile: &JsonTile) -> String {
+    let summary = json_tile.payload.as_ref().and_then(|payload| payload.get("summary")).and_then(Value::as_str);
+
+    match (summary, &json_tile.id) {
+        (Some(summary), _) => summary.to_string(),
+        (None, Value::String(label)) => label.clone(),
+        (None, id) => id.to_string()
+    }
+}
 
-fn get_tiling_method(s: &str) -> std::boxed::Box<dyn tiling_method::TilingMethod> {
-    match s.parse::<tiling_method::TilingMethodName>() {
-        Ok(tiling_method::TilingMethodName::SpaceFilling) => {
-            Box::new(space_filling_tiling_method::SpaceFillingTilingMethod::new())
+// iCalendar days can be 23 or 25 hours long, and their grid reads the wall clock.
+#[cfg(feature = "ics")]
+fn render_ics_day_svg(tiles: &[Tile], events: &[Event], labels: &[String], day: &IcsDay) -> String {
+    let svg_options = SvgOptions {
+        end_hour: day.get_hours(),
+        ..SvgOptions::default()
+    };
+
+    svg::render_day_svg(tiles, events, Some(labels), day, &svg_options)
+}
+
+#[cfg(not(feature = "ics"))]
+fn render_ics_day_svg(_tiles: &[Tile], _events: &[Event], _labels: &[String], day: &IcsDay) -> String {
+    match *day {}
+}
+
+fn write_tiles(
+    writer: &mut dyn Write,
+    json_tiles: &[JsonTile],
+    events: &[Event],
+    ics_day: Option<&IcsDay>,
+    format: cli::Format
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        cli::Format::Json => {
+            serde_json::to_writer_pretty(&mut *writer, json_tiles)?;
+            writeln!(writer)?;
         },
-        Ok(tiling_method::TilingMethodName::TimeRespective) => {
-            Box::new(time_respective_tiling_method::TimeRespectiveTilingMethod::new())
+        cli::Format::Debug => {
+            for json_tile in json_tiles.iter() {
+                writeln!(writer, "({}, {:?})", json_tile.id, json_tile.tile)?;
+            }
         },
-        _ => {
-            Box::new(basic_tiling_method::BasicTilingMethod::new())
+        cli::Format::Svg => {
+            let tiles: Vec<Tile> = json_tiles.iter().map(|json_tile| json_tile.tile).collect();
+            let labels: Vec<String> = json_tiles.iter().map(get_label).collect();
+
+            let svg = match ics_day {
+                Some(day) => render_ics_day_svg(&tiles, events, &labels, day),
+                None => svg::render_svg(&tiles, events, Some(&labels), &SvgOptions::default())
+            };
+
+            write!(writer, "{}", svg)?;
         }
     }
+
+    Ok(())
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+fn list_methods() {
+    let width = TilingMethodName::ALL.iter().map(|name| name.name().len()).max().unwrap_or(0) + 2;
 
-    let tiling_method_arg = if args.len() > 1 {
-        args[1].to_string()
-    } else {
-        (tiling_method::TilingMethodName::Basic as i32).to_string()
+    for tiling_method_name in TilingMethodName::ALL.iter() {
+        println!("{:<width$}{}", tiling_method_name.name(), tiling_method_name.description(), width = width);
+    }
+}
+
+fn run(options: cli::Options) -> Result<(), Box<dyn Error>> {
+    let (json_events, ics_day) = match &options.input {
+        Some(input) => read_events(input, &options)?,
+        None => (generate_random_events(options.seed, options.count), None)
     };
 
-    let tiling_method = get_tiling_method(&tiling_method_arg);
-    let mut rng = rand::thread_rng();
-    let number_of_events: u128 = rng.gen_range(1..MAX_NUMBER_OF_EVENTS as u128);
-    let mut events: Vec<event::Event> = vec![];
+    let events: Vec<Event> = json_events.iter().map(|json_event| json_event.event).collect();
 
-    for _ in 0 .. number_of_events {
-        let start: f64 = rng.gen_range(0_f64..HOURS_PER_DAY as f64);
-        let end: f64 = rng.gen_range(start..(HOURS_PER_DAY as f64));
+    let json_tiles = json::tile_json_events(
+        json_events,
+        TilerOptions::new(options.error_bound),
+        get_tiling_method_with_objective(options.tiling_method_name, options.objective.unwrap_or_default())
+    )?;
 
-        events.push(event::Event {
-            start: start,
-            end: end
-        });
-    }
+    let mut writer: Box<dyn Write> = match &options.output {
+        Some(output) => Box::new(BufWriter::new(File::create(output)?)),
+        None => Box::new(BufWriter::new(io::stdout()))
+    };
+
+    write_tiles(&mut writer, &json_tiles, &events, ics_day.as_ref(), options.format)?;
+    writer.flush()?;
+
+    Ok(())
+}
 
-    let mut tiler = tiler::Tiler::new(
-        events,
-        tiler_options::TilerOptions::new(0.0001),
-        tiling_method
-    );
+fn main() {
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    let result = match command {
+        cli::Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        },
+        cli::Command::ListMethods => {
+            list_methods();
+            Ok(())
+        },
+        cli::Command::Tile(options) => run(options)
+    };
 
-    tiler.tile();
-    tiler.print_tiles();
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
 }
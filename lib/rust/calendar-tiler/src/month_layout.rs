@@ -0,0 +1,112 @@
+use crate::event;
+use crate::tiler;
+use crate::tiler_options;
+use crate::tiler_error;
+use crate::week_tiler;
+
+pub const DAYS_PER_WEEK: usize = 7;
+
+/// A horizontal bar within one week row of a month grid.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct MonthBar {
+  pub original_index: usize,
+  pub week_index: usize,
+  pub row_index: usize,
+  /// Day of the week the bar starts on.
+  pub start_cell: usize,
+  /// Day of the week after the last one the bar covers.
+  pub end_cell: usize,
+  /// Whether the event started before this week row.
+  pub continues_before: bool,
+  /// Whether the event ends after this week row.
+  pub continues_after: bool,
+  /// Whether the bar falls past the row limit, and only shows in the overflow
+  /// counts of its cells.
+  pub hidden: bool,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct MonthLayout {
+  /// Bars ordered by week, then by row, then by start cell.
+  pub bars: Vec<MonthBar>,
+  /// Number of hidden bars covering each cell, by week and day of the week.
+  pub overflow_counts: Vec<[usize; DAYS_PER_WEEK]>,
+}
+
+/// Lays out events with absolute times, as `WeekTiler` takes them, as bars on
+/// a grid of `number_of_weeks` rows starting at the midnight `month_start`.
+/// Each week row is packed like the columns of a day turned on its side: bars
+/// are placed by start, longest first, in the lowest row free on their first
+/// cell. Bars in rows from `max_rows` on are hidden.
+pub fn layout_month(
+  events: &[event::Event],
+  month_start: f64,
+  number_of_weeks: usize,
+  max_rows: Option<usize>,
+  tiler_options: &tiler_options::TilerOptions
+) -> Result<MonthLayout, tiler_error::TilerError> {
+  tiler::validate_events(events)?;
+
+  let number_of_days = number_of_weeks * DAYS_PER_WEEK;
+  let get_day_start = |day_index: usize| month_start + (day_index as f64) * week_tiler::SECONDS_PER_DAY;
+  let mut weeks: Vec<Vec<MonthBar>> = vec![vec![]; number_of_weeks];
+
+  for (original_index, event) in events.iter().enumerate() {
+    let covered_days = week_tiler::get_covered_days(event, month_start, number_of_days);
+
+    if covered_days.is_empty() {
+      continue;
+    }
+
+    let covered_weeks = (covered_days.start / DAYS_PER_WEEK) .. ((covered_days.end - 1) / DAYS_PER_WEEK + 1);
+
+    for (week_index, week_bars) in covered_weeks.clone().zip(weeks[covered_weeks].iter_mut()) {
+      let week_start_day = week_index * DAYS_PER_WEEK;
+      let first_day = covered_days.start.max(week_start_day);
+      let end_day = covered_days.end.min(week_start_day + DAYS_PER_WEEK);
+
+      week_bars.push(MonthBar {
+        original_index,
+        week_index,
+        row_index: 0,
+        start_cell: first_day - week_start_day,
+        end_cell: end_day - week_start_day,
+        continues_before: event.start < get_day_start(first_day),
+        continues_after: event.end > get_day_start(end_day),
+        hidden: false
+      });
+    }
+  }
+
+  let mut month_layout = MonthLayout {
+    bars: vec![],
+    overflow_counts: vec![[0; DAYS_PER_WEEK]; number_of_weeks],
+  };
+
+  for (week_index, week_bars) in weeks.iter().enumerate() {
+    // Columns never reuse a column at the instant it frees up, so bars end
+    // half a cell early to let one starting in the next cell share a row.
+    let cell_events: Vec<event::Event> = week_bars.iter().map(|bar| {
+      event::Event::new(bar.start_cell as f64, bar.end_cell as f64 - 0.5)
+    }).collect();
+    let rows = tiler::generate_columns(&tiler::sort_events(&cell_events, tiler_options), tiler_options);
+
+    for (row_index, row) in rows.iter().enumerate() {
+      let hidden = max_rows.is_some_and(|max_rows| row_index >= max_rows);
+
+      for sorted_event in row.iter() {
+        let bar = MonthBar { row_index, hidden, ..week_bars[sorted_event.original_index] };
+
+        if hidden {
+          for count in month_layout.overflow_counts[week_index][bar.start_cell .. bar.end_cell].iter_mut() {
+            *count += 1;
+          }
+        }
+
+        month_layout.bars.push(bar);
+      }
+    }
+  }
+
+  Ok(month_layout)
+}
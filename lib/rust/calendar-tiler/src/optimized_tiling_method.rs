@@ -0,0 +1,225 @@
+use crate::sorted_event;
+use crate::tile;
+use crate::tiler;
+use crate::tiling_method;
+use crate::tiler_options;
+use crate::tiler_error;
+use crate::overlap_cluster;
+use crate::dag;
+use crate::dag_builder;
+use crate::simplex;
+
+/// The dense tableau grows with the square of the cluster, so larger clusters
+/// are not solved, and fall back as `OptimizedTilingMethod` describes.
+pub const MAX_OPTIMIZED_CLUSTER_SIZE: usize = 64;
+
+/// Links every event to the overlapping events in columns to its right, so
+/// every pair of overlapping events is ordered left to right. Events are swept
+/// by start, and each is only compared with the events still in progress.
+fn build_order(
+  sorted_events: &[sorted_event::SortedEvent],
+  columns: &[Vec<sorted_event::SortedEvent>],
+  tiler_options: &tiler_options::TilerOptions
+) -> dag_builder::DagBuilder {
+  let mut order = dag_builder::DagBuilder::new(sorted_events.len() as u128);
+  let mut column_indices: Vec<usize> = vec![0; sorted_events.len()];
+  let mut in_progress: Vec<usize> = vec![];
+
+  for (column_index, column) in columns.iter().enumerate() {
+    for sorted_event in column.iter() {
+      column_indices[sorted_event.sorted_index] = column_index;
+    }
+  }
+
+  for sorted_event in sorted_events.iter() {
+    in_progress.retain(|&i| sorted_events[i].event.end - tiler_options.error_bound > sorted_event.event.start);
+
+    for &i in in_progress.iter() {
+      if sorted_events[i].event.overlaps(&sorted_event.event, tiler_options.error_bound) {
+        if column_indices[i] < column_indices[sorted_event.sorted_index] {
+          order.add_edge(i, sorted_event.sorted_index);
+        } else {
+          order.add_edge(sorted_event.sorted_index, i);
+        }
+      }
+    }
+
+    in_progress.push(sorted_event.sorted_index);
+  }
+
+  order
+}
+
+/// Edges of the DAG that no longer path already implies, which are the only
+/// ones the program needs as constraints.
+fn get_reduced_edges(
+  forward_dag: &dag::DirectedAcyclicGraph,
+  number_of_vertices: usize
+) -> Vec<(usize, usize)> {
+  let mut descendants: Vec<Vec<bool>> = vec![vec![]; number_of_vertices];
+  let mut edges: Vec<(usize, usize)> = vec![];
+
+  for &vertex in forward_dag.get_sorted_vertices().iter() {
+    let mut reachable_through_others = vec![false; number_of_vertices];
+    let mut vertex_descendants = vec![false; number_of_vertices];
+
+    for &to_vertex in forward_dag.get_edges(vertex).iter() {
+      vertex_descendants[to_vertex] = true;
+
+      for (i, &is_descendant) in descendants[to_vertex].iter().enumerate() {
+        if is_descendant {
+          reachable_through_others[i] = true;
+          vertex_descendants[i] = true;
+        }
+      }
+    }
+
+    for &to_vertex in forward_dag.get_edges(vertex).iter() {
+      if !reachable_through_others[to_vertex] {
+        edges.push((vertex, to_vertex));
+      }
+    }
+
+    descendants[vertex] = vertex_descendants;
+  }
+
+  edges
+}
+
+/// Places every tile of a cluster left to right as the space-filling method
+/// would order them, as a linear program over each tile's `x` and its width
+/// beyond the minimum. Widths are weighted by duration, so the program fills
+/// as much of the cluster's area as the order allows.
+fn solve_cluster(
+  cluster: &[sorted_event::SortedEvent],
+  edges: &[(usize, usize)],
+  min_width: f64
+) -> Result<Vec<(f64, f64)>, simplex::SimplexError> {
+  let number_of_events = cluster.len();
+  let mut objective = vec![0.0; 2 * number_of_events];
+  let mut constraints: Vec<simplex::Constraint> = vec![];
+
+  for (i, sorted_event) in cluster.iter().enumerate() {
+    objective[number_of_events + i] = sorted_event.event.end - sorted_event.event.start;
+
+    constraints.push(simplex::Constraint {
+      coefficients: vec![(i, 1.0), (number_of_events + i, 1.0)],
+      bound: 1.0 - min_width,
+    });
+  }
+
+  for &(from_vertex, to_vertex) in edges.iter() {
+    constraints.push(simplex::Constraint {
+      coefficients: vec![(from_vertex, 1.0), (number_of_events + from_vertex, 1.0), (to_vertex, -1.0)],
+      bound: -min_width,
+    });
+  }
+
+  let solution = simplex::maximize(&objective, &constraints)?;
+
+  Ok((0 .. number_of_events).map(|i| (solution[i], min_width + solution[number_of_events + i])).collect())
+}
+
+fn tile_cluster(
+  cluster: &[sorted_event::SortedEvent],
+  tiles: &mut [tile::Tile],
+  tiler_options: &tiler_options::TilerOptions,
+  strict: bool
+) -> Result<(), tiler_error::TilerError> {
+  let local_cluster: Vec<sorted_event::SortedEvent> = cluster.iter().enumerate().map(|(i, sorted_event)| {
+    sorted_event::SortedEvent { sorted_index: i, ..*sorted_event }
+  }).collect();
+
+  // Events that only touch leave each other unconstrained, so they may share
+  // a column here, which keeps them out of the same chain.
+  let columns = tiler::generate_columns(
+    &local_cluster,
+    &tiler_options::TilerOptions { error_bound: 0.0, ..*tiler_options }
+  );
+  let mut order = build_order(&local_cluster, &columns, tiler_options);
+
+  // Every chain of overlapping events shares the width, so no layout in this
+  // order has a wider minimum than one over the longest chain, and spreading
+  // the width evenly along it reaches that. The program keeps every width at
+  // least that wide, so `min_width` holds whenever it is no wider.
+  order.forward_dag.topological_sort();
+
+  let longest_chain = order.forward_dag.get_longest_path_lengths_through().into_iter().max().unwrap_or(1);
+  let max_min_width = 1.0 / (longest_chain as f64);
+
+  if max_min_width < tiler_options.min_width - tiler_options.error_bound {
+    return Err(tiler_error::TilerError::UnsatisfiableMinWidth {
+      min_width: tiler_options.min_width,
+      max_min_width
+    });
+  }
+
+  let offset = cluster[0].sorted_index;
+  let cluster_tiles = &mut tiles[offset .. offset + cluster.len()];
+  let solution = if cluster.len() > MAX_OPTIMIZED_CLUSTER_SIZE {
+    Err(tiler_error::FallbackReason::ClusterTooLarge)
+  } else {
+    let edges = get_reduced_edges(&order.forward_dag, cluster.len());
+
+    // The program is always feasible and bounded, so this only fails when the
+    // solver loses its way to rounding.
+    solve_cluster(&local_cluster, &edges, max_min_width).map_err(tiler_error::FallbackReason::SolverFailed)
+  };
+
+  match solution {
+    Ok(positions) => {
+      for (tile, (x, dx)) in cluster_tiles.iter_mut().zip(positions) {
+        tile.x = x;
+        tile.dx = dx;
+      }
+    },
+    Err(reason) if strict => {
+      return Err(tiler_error::TilerError::OptimizationFallback {
+        sorted_index: offset,
+        number_of_events: cluster.len(),
+        reason
+      });
+    },
+    // Each tile takes an even share of the width left of it along its longest
+    // chain ahead, which keeps every tile at least `max_min_width` wide.
+    Err(_) => order.update_tiles(cluster_tiles)
+  }
+
+  Ok(())
+}
+
+/// Solves each overlap cluster for the widest minimum width, then for the
+/// most area covered. Clusters over `MAX_OPTIMIZED_CLUSTER_SIZE` events, or
+/// ones the solver fails on, fall back to even shares along their longest
+/// chains. That still reaches the widest minimum width, but not necessarily
+/// the most area. A `strict` method returns
+/// `TilerError::OptimizationFallback` for those clusters instead.
+#[derive(Default)]
+pub struct OptimizedTilingMethod {
+  pub strict: bool,
+}
+
+impl OptimizedTilingMethod {
+  pub fn new() -> OptimizedTilingMethod {
+    OptimizedTilingMethod { strict: false }
+  }
+
+  pub fn strict() -> OptimizedTilingMethod {
+    OptimizedTilingMethod { strict: true }
+  }
+}
+
+impl tiling_method::TilingMethod for OptimizedTilingMethod {
+  fn tile(
+    &self,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
+    tiler_options: &tiler_options::TilerOptions
+  ) -> Result<(), tiler_error::TilerError> {
+    for range in overlap_cluster::get_cluster_ranges(sorted_events, tiler_options) {
+      tile_cluster(&sorted_events[range], tiles, tiler_options, self.strict)?;
+    }
+
+    Ok(())
+  }
+}
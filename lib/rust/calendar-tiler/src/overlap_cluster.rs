@@ -0,0 +1,97 @@
+use std::ops::Range;
+use crate::sorted_event;
+use crate::tiler_options;
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct OverlapCluster {
+  pub start: f64,
+  pub end: f64,
+  pub sorted_indices: Range<usize>,
+  pub original_indices: Vec<usize>,
+  pub max_concurrency: usize,
+}
+
+impl OverlapCluster {
+  pub fn len(&self) -> usize {
+    self.sorted_indices.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.sorted_indices.is_empty()
+  }
+}
+
+/// Splits `sorted_events` into runs where every event overlaps an earlier
+/// event of the same run. Events in different runs never overlap, and events
+/// that only touch, within `error_bound`, start a new run.
+pub fn get_cluster_ranges(
+  sorted_events: &[sorted_event::SortedEvent],
+  tiler_options: &tiler_options::TilerOptions
+) -> Vec<Range<usize>> {
+  let mut ranges: Vec<Range<usize>> = vec![];
+  let mut cluster_start: usize = 0;
+  let mut cluster_end: f64 = f64::NEG_INFINITY;
+
+  for (i, sorted_event) in sorted_events.iter().enumerate() {
+    if i > cluster_start && sorted_event.event.start >= cluster_end - tiler_options.error_bound {
+      ranges.push(cluster_start .. i);
+      cluster_start = i;
+    }
+
+    cluster_end = if i == cluster_start {
+      sorted_event.event.end
+    } else {
+      cluster_end.max(sorted_event.event.end)
+    };
+  }
+
+  if cluster_start < sorted_events.len() {
+    ranges.push(cluster_start .. sorted_events.len());
+  }
+
+  ranges
+}
+
+// Sweeps the events by start, keeping those that have not yet ended. The ones
+// an event overlaps all overlap each other too, so the largest such group,
+// with the event itself, is the most events that overlap at once.
+fn get_max_concurrency(
+  sorted_events: &[sorted_event::SortedEvent],
+  tiler_options: &tiler_options::TilerOptions
+) -> usize {
+  let mut max_concurrency: usize = 0;
+  let mut in_progress: Vec<&sorted_event::SortedEvent> = vec![];
+
+  for sorted_event in sorted_events.iter() {
+    in_progress.retain(|other| other.event.end - tiler_options.error_bound > sorted_event.event.start);
+
+    let concurrency = 1 + in_progress.iter()
+                                     .filter(|other| other.event.overlaps(&sorted_event.event, tiler_options.error_bound))
+                                     .count();
+
+    max_concurrency = max_concurrency.max(concurrency);
+    in_progress.push(sorted_event);
+  }
+
+  max_concurrency
+}
+
+/// Groups `sorted_events` into connected overlap clusters, in order of start.
+/// A cluster's maximum concurrency is the most of its events that overlap at
+/// once, where events that only touch, within `error_bound`, do not overlap.
+pub fn find_overlap_clusters(
+  sorted_events: &[sorted_event::SortedEvent],
+  tiler_options: &tiler_options::TilerOptions
+) -> Vec<OverlapCluster> {
+  get_cluster_ranges(sorted_events, tiler_options).into_iter().map(|range| {
+    let members = &sorted_events[range.clone()];
+
+    OverlapCluster {
+      start: members[0].event.start,
+      end: members.iter().map(|sorted_event| sorted_event.event.end).fold(f64::NEG_INFINITY, f64::max),
+      original_indices: members.iter().map(|sorted_event| sorted_event.original_index).collect(),
+      max_concurrency: get_max_concurrency(members, tiler_options),
+      sorted_indices: range,
+    }
+  }).collect()
+}
@@ -0,0 +1,214 @@
+use std::fmt::{self, Formatter, Display};
+
+const EPSILON: f64 = 0.000000001;
+
+/// The constraint `coefficients · x <= bound`, listing only the non-zero
+/// coefficients as `(variable, coefficient)` pairs.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Constraint {
+  pub coefficients: Vec<(usize, f64)>,
+  pub bound: f64,
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum SimplexError {
+  Infeasible,
+  Unbounded,
+}
+
+impl Display for SimplexError {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    match self {
+      SimplexError::Infeasible => write!(f, "no point satisfies every constraint"),
+      SimplexError::Unbounded => write!(f, "the objective grows without bound"),
+    }
+  }
+}
+
+impl std::error::Error for SimplexError {}
+
+struct Tableau {
+  rows: Vec<Vec<f64>>,
+  objective: Vec<f64>,
+  basis: Vec<usize>,
+  rhs_column: usize,
+}
+
+impl Tableau {
+  fn pivot(
+    &mut self,
+    pivot_row: usize,
+    pivot_column: usize
+  ) {
+    let pivot = self.rows[pivot_row][pivot_column];
+
+    for value in self.rows[pivot_row].iter_mut() {
+      *value /= pivot;
+    }
+
+    let (before, rest) = self.rows.split_at_mut(pivot_row);
+    let (row, after) = rest.split_first_mut().unwrap();
+
+    for other_row in before.iter_mut().chain(after.iter_mut()).chain(std::iter::once(&mut self.objective)) {
+      let factor = other_row[pivot_column];
+
+      if factor == 0.0 {
+        continue;
+      }
+
+      for (value, row_value) in other_row.iter_mut().zip(row.iter()) {
+        *value -= factor * row_value;
+      }
+    }
+
+    self.basis[pivot_row] = pivot_column;
+  }
+
+  /// Pivots until no column below `number_of_columns` improves the objective.
+  /// Bland's rule, taking the lowest eligible column and basic variable, keeps
+  /// degenerate pivots from cycling.
+  fn optimize(
+    &mut self,
+    number_of_columns: usize
+  ) -> Result<(), SimplexError> {
+    loop {
+      let pivot_column = match (0 .. number_of_columns).find(|&column| self.objective[column] < -EPSILON) {
+        Some(column) => column,
+        None => return Ok(()),
+      };
+
+      let mut pivot_row: Option<usize> = None;
+      let mut min_ratio = f64::INFINITY;
+
+      for (i, row) in self.rows.iter().enumerate() {
+        if row[pivot_column] <= EPSILON {
+          continue;
+        }
+
+        let ratio = row[self.rhs_column] / row[pivot_column];
+        let is_better = match pivot_row {
+          None => true,
+          Some(best_row) => ratio < min_ratio - EPSILON
+            || (ratio < min_ratio + EPSILON && self.basis[i] < self.basis[best_row]),
+        };
+
+        if is_better {
+          pivot_row = Some(i);
+          min_ratio = ratio;
+        }
+      }
+
+      match pivot_row {
+        Some(row) => self.pivot(row, pivot_column),
+        None => return Err(SimplexError::Unbounded),
+      }
+    }
+  }
+}
+
+/// Maximizes `objective · x` subject to every constraint and `x >= 0`, with the
+/// two-phase simplex method on a dense tableau. Bounds may be negative, in
+/// which case the first phase searches for a feasible starting point.
+pub fn maximize(
+  objective: &[f64],
+  constraints: &[Constraint]
+) -> Result<Vec<f64>, SimplexError> {
+  let number_of_variables = objective.len();
+  let number_of_constraints = constraints.len();
+  let number_of_artificials = constraints.iter().filter(|constraint| constraint.bound < 0.0).count();
+
+  // Columns hold the variables, then one slack per constraint, then one
+  // artificial variable per constraint with a negative bound.
+  let real_columns = number_of_variables + number_of_constraints;
+  let rhs_column = real_columns + number_of_artificials;
+  let mut tableau = Tableau {
+    rows: vec![vec![0.0; rhs_column + 1]; number_of_constraints],
+    objective: vec![0.0; rhs_column + 1],
+    basis: vec![0; number_of_constraints],
+    rhs_column,
+  };
+  let mut next_artificial = real_columns;
+
+  for (i, constraint) in constraints.iter().enumerate() {
+    let sign = if constraint.bound < 0.0 { -1.0 } else { 1.0 };
+    let row = &mut tableau.rows[i];
+
+    for &(variable, coefficient) in constraint.coefficients.iter() {
+      row[variable] += sign * coefficient;
+    }
+
+    row[number_of_variables + i] = sign;
+    row[rhs_column] = sign * constraint.bound;
+
+    if constraint.bound < 0.0 {
+      row[next_artificial] = 1.0;
+      tableau.basis[i] = next_artificial;
+      next_artificial += 1;
+    } else {
+      tableau.basis[i] = number_of_variables + i;
+    }
+  }
+
+  // The first phase maximizes minus the sum of the artificial variables, which
+  // reaches zero exactly when the constraints can all be met.
+  if number_of_artificials > 0 {
+    for column in real_columns .. rhs_column {
+      tableau.objective[column] = 1.0;
+    }
+
+    for i in 0 .. number_of_constraints {
+      if tableau.basis[i] >= real_columns {
+        for (value, row_value) in tableau.objective.iter_mut().zip(tableau.rows[i].iter()) {
+          *value -= row_value;
+        }
+      }
+    }
+
+    tableau.optimize(rhs_column)?;
+
+    if tableau.objective[rhs_column] < -EPSILON * (number_of_constraints as f64).max(1.0) {
+      return Err(SimplexError::Infeasible);
+    }
+
+    // Artificial variables left in the basis are zero, and are swapped out
+    // wherever their row still constrains a real column.
+    for i in 0 .. number_of_constraints {
+      if tableau.basis[i] >= real_columns {
+        if let Some(column) = (0 .. real_columns).find(|&column| tableau.rows[i][column].abs() > EPSILON) {
+          tableau.pivot(i, column);
+        }
+      }
+    }
+  }
+
+  tableau.objective = vec![0.0; rhs_column + 1];
+
+  for (value, coefficient) in tableau.objective.iter_mut().zip(objective.iter()) {
+    *value = -coefficient;
+  }
+
+  for i in 0 .. number_of_constraints {
+    let basic_coefficient = objective.get(tableau.basis[i]).copied().unwrap_or(0.0);
+
+    if basic_coefficient != 0.0 {
+      for (value, row_value) in tableau.objective.iter_mut().zip(tableau.rows[i].iter()) {
+        *value += basic_coefficient * row_value;
+      }
+    }
+  }
+
+  tableau.optimize(real_columns)?;
+
+  let mut solution = vec![0.0; number_of_variables];
+
+  for (i, &column) in tableau.basis.iter().enumerate() {
+    if column < number_of_variables {
+      solution[column] = tableau.rows[i][rhs_column];
+    }
+  }
+
+  Ok(solution)
+}
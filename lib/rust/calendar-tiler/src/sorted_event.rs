@@ -2,7 +2,7 @@ use std::fmt::{self, Formatter, Display};
 use crate::event;
 use crate::tile;
 
-#[derive(PartialEq, PartialOrd, Copy, Clone)]
+#[derive(PartialEq, PartialOrd, Copy, Clone, Debug)]
 pub struct SortedEvent {
   pub event: event::Event,
   pub original_index: usize,
@@ -3,18 +3,8 @@ use crate::tile;
 use crate::tiler;
 use crate::tiling_method;
 use crate::tiler_options;
+use crate::tiler_error;
 use crate::dag_builder;
-use crate::dag;
-
-fn add_edges_to_dags(
-  dag: &mut dag::DirectedAcyclicGraph,
-  from_vertex: usize,
-  to_vertices: Vec<usize>
-) {
-  for i in 0 .. to_vertices.len() {
-    dag.add_edge(from_vertex, to_vertices[i]);
-  }
-}
 
 fn get_sorted_events_collision_index(
   sorted_event_a: &sorted_event::SortedEvent,
@@ -55,16 +45,16 @@ fn get_sorted_events_collision_index(
 }
 
 fn collide_sorted_event_into_column(
-  column: &Vec<sorted_event::SortedEvent>,
+  column: &[sorted_event::SortedEvent],
   sorted_event: &sorted_event::SortedEvent,
   in_reverse: bool
 ) -> Option<Vec<usize>> {
   let mut to_vertices: Vec<usize> = vec![];
 
-  for i in 0 .. column.len() {
+  for column_sorted_event in column.iter() {
     let collision_index = get_sorted_events_collision_index(
       sorted_event,
-      &column[i],
+      column_sorted_event,
       in_reverse
     );
 
@@ -80,7 +70,7 @@ fn collide_sorted_event_into_column(
     }
   }
 
-  if to_vertices.len() > 0 {
+  if !to_vertices.is_empty() {
     Some(to_vertices)
   } else {
     None
@@ -88,7 +78,7 @@ fn collide_sorted_event_into_column(
 }
 
 fn get_backward_to_vertices(
-  columns: &Vec<Vec<sorted_event::SortedEvent>>,
+  columns: &[Vec<sorted_event::SortedEvent>],
   sorted_event: &sorted_event::SortedEvent,
   column_index: usize
 ) -> Vec<usize> {
@@ -104,37 +94,37 @@ fn get_backward_to_vertices(
 }
 
 fn get_extended_forward_to_vertices(
-  sorted_events: &Vec<sorted_event::SortedEvent>,
-  columns: &Vec<Vec<sorted_event::SortedEvent>>,
+  sorted_events: &[sorted_event::SortedEvent],
+  columns: &[Vec<sorted_event::SortedEvent>],
   sorted_event: &sorted_event::SortedEvent,
   linchpin_sorted_event: &sorted_event::SortedEvent,
   column_index: usize
 ) -> Vec<usize> {
   let mut to_vertices: Vec<usize> = vec![];
 
-  for i in (column_index + 1) .. columns.len() {
-    let extended_to_vertices = collide_sorted_event_into_column(&columns[i], sorted_event, false);
+  for column in columns.iter().skip(column_index + 1) {
+    let extended_to_vertices = collide_sorted_event_into_column(column, sorted_event, false);
 
     if let Some(extended_to_vertices_vec) = extended_to_vertices {
-      for j in 0 .. extended_to_vertices_vec.len() {
+      for extended_to_vertex in extended_to_vertices_vec {
         let collision_index = get_sorted_events_collision_index(
           linchpin_sorted_event,
-          &sorted_events[extended_to_vertices_vec[j]],
+          &sorted_events[extended_to_vertex],
           false
         );
 
-        let mut should_return: bool = false;
-        
+        let mut collides_with_linchpin: bool = false;
+
         if let Some(collision_index_int) = collision_index {
           if collision_index_int > -1 {
-            should_return = true;
+            collides_with_linchpin = true;
           }
         }
 
-        if should_return {
-          return to_vertices;
-        } else {
-          to_vertices.push(extended_to_vertices_vec[j]);
+        // The linchpin reaches this one, but not necessarily the ones in the
+        // columns after it.
+        if !collides_with_linchpin {
+          to_vertices.push(extended_to_vertex);
         }
       }
     }
@@ -144,8 +134,8 @@ fn get_extended_forward_to_vertices(
 }
 
 fn get_forward_to_vertices(
-  sorted_events: &Vec<sorted_event::SortedEvent>,
-  columns: &Vec<Vec<sorted_event::SortedEvent>>,
+  sorted_events: &[sorted_event::SortedEvent],
+  columns: &[Vec<sorted_event::SortedEvent>],
   sorted_event: &sorted_event::SortedEvent,
   column_index: usize
 ) -> Vec<usize> {
@@ -155,7 +145,7 @@ fn get_forward_to_vertices(
     if let Some(to_vertices_vec) = to_vertices {
       let linchpin_sorted_event = sorted_events[to_vertices_vec[0]];
 
-      if to_vertices_vec.len() > 0 && sorted_event.event.end > linchpin_sorted_event.event.start {
+      if !to_vertices_vec.is_empty() && sorted_event.event.end > linchpin_sorted_event.event.start {
         return [
           to_vertices_vec,
           get_extended_forward_to_vertices(
@@ -175,6 +165,31 @@ fn get_forward_to_vertices(
   vec![] as Vec<usize>
 }
 
+/// Links every event to its colliding events in the nearest columns on
+/// either side, in separate DAGs for each direction.
+#[doc(hidden)]
+pub fn build_dag_builder(
+  sorted_events: &[sorted_event::SortedEvent],
+  columns: &[Vec<sorted_event::SortedEvent>]
+) -> dag_builder::DagBuilder {
+  let mut dag_builder = dag_builder::DagBuilder::new(sorted_events.len() as u128);
+
+  for (i, column) in columns.iter().enumerate() {
+    for sorted_event in column.iter() {
+      for to_vertex in get_backward_to_vertices(columns, sorted_event, i) {
+        dag_builder.add_edge(to_vertex, sorted_event.sorted_index);
+      }
+
+      for to_vertex in get_forward_to_vertices(sorted_events, columns, sorted_event, i) {
+        dag_builder.add_edge(sorted_event.sorted_index, to_vertex);
+      }
+    }
+  }
+
+  dag_builder
+}
+
+#[derive(Default)]
 pub struct SpaceFillingTilingMethod;
 
 impl SpaceFillingTilingMethod {
@@ -186,44 +201,18 @@ impl SpaceFillingTilingMethod {
 impl tiling_method::TilingMethod for SpaceFillingTilingMethod {
    fn tile(
     &self,
-    tiles: &mut Vec<tile::Tile>,
-    sorted_events: &Vec<sorted_event::SortedEvent>,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
     tiler_options: &tiler_options::TilerOptions
-  ) {
-    if sorted_events.len() == 0 {
-      return;
+  ) -> Result<(), tiler_error::TilerError> {
+    if sorted_events.is_empty() {
+      return Ok(());
     }
 
     let columns = tiler::generate_columns(sorted_events, tiler_options);
-    let mut dag_builder = dag_builder::DagBuilder::new(sorted_events.len() as u128);
-
-    for i in 0 .. columns.len() {
-      let column = &columns[i];
-
-      for j in 0 .. column.len() {
-        add_edges_to_dags(
-          &mut dag_builder.backward_dag,
-          column[j].sorted_index,
-          get_backward_to_vertices(
-            &columns,
-            &column[j],
-            i
-          )
-        );
 
-        add_edges_to_dags(
-          &mut dag_builder.forward_dag,
-          column[j].sorted_index,
-          get_forward_to_vertices(
-            sorted_events,
-            &columns,
-            &column[j],
-            i
-          )
-        );
-      }
-    }
+    build_dag_builder(sorted_events, &columns).update_tiles(tiles);
 
-    dag_builder.update_tiles(tiles);
+    Ok(())
   }
 }
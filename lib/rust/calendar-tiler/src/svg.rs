@@ -0,0 +1,176 @@
+use crate::event;
+use crate::tile;
+#[cfg(feature = "timezone")]
+use crate::time;
+
+const HOURS_PER_DAY: f64 = 24.0;
+const GUTTER_WIDTH: f64 = 48.0;
+const LABEL_PADDING: f64 = 4.0;
+const FONT_SIZE: f64 = 11.0;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct SvgOptions {
+  pub width: f64,
+  pub hour_height: f64,
+  pub start_hour: f64,
+  pub end_hour: f64,
+}
+
+impl SvgOptions {
+  pub fn new(
+    width: f64,
+    hour_height: f64
+  ) -> SvgOptions {
+    SvgOptions {
+      width,
+      hour_height,
+      start_hour: 0.0,
+      end_hour: HOURS_PER_DAY,
+    }
+  }
+}
+
+impl Default for SvgOptions {
+  fn default() -> SvgOptions {
+    SvgOptions::new(480.0, 40.0)
+  }
+}
+
+fn escape_xml(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+
+  for c in text.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&apos;"),
+      _ => escaped.push(c)
+    }
+  }
+
+  escaped
+}
+
+fn format_time(hours: f64) -> String {
+  let total_minutes = (hours * 60.0).round() as i64;
+
+  format!("{}:{:02}", total_minutes / 60, total_minutes % 60)
+}
+
+// Positions on the view are labelled with the time `get_clock_time` reads
+// there, so days with an offset change can show the wall clock.
+fn render(
+  tiles: &[tile::Tile],
+  events: &[event::Event],
+  labels: Option<&[String]>,
+  svg_options: &SvgOptions,
+  get_clock_time: &dyn Fn(f64) -> f64
+) -> String {
+  let column_width = svg_options.width - GUTTER_WIDTH;
+  let height = (svg_options.end_hour - svg_options.start_hour) * svg_options.hour_height;
+  let mut svg = String::new();
+
+  svg.push_str(&format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"{2}\">\n",
+    svg_options.width,
+    height,
+    FONT_SIZE
+  ));
+
+  svg.push_str("  <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n");
+
+  let mut hour = svg_options.start_hour.ceil();
+
+  while hour <= svg_options.end_hour {
+    let y = (hour - svg_options.start_hour) * svg_options.hour_height;
+
+    svg.push_str(&format!(
+      "  <line x1=\"{0}\" y1=\"{1}\" x2=\"{2}\" y2=\"{1}\" stroke=\"#dddddd\"/>\n",
+      GUTTER_WIDTH,
+      y,
+      svg_options.width
+    ));
+
+    svg.push_str(&format!(
+      "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\" fill=\"#666666\">{}</text>\n",
+      GUTTER_WIDTH - LABEL_PADDING,
+      y + FONT_SIZE,
+      format_time(get_clock_time(hour))
+    ));
+
+    hour += 1.0;
+  }
+
+  // Stable, so tiles on the same layer keep their order.
+  let mut stacking_order: Vec<usize> = (0 .. tiles.len()).collect();
+
+  stacking_order.sort_by_key(|&i| tiles[i].z);
+
+  for i in stacking_order {
+    let tile = &tiles[i];
+    let x = GUTTER_WIDTH + tile.x * column_width;
+    let y = (tile.y - svg_options.start_hour) * svg_options.hour_height;
+    let width = tile.dx * column_width;
+    let tile_height = tile.dy * svg_options.hour_height;
+
+    svg.push_str("  <g>\n");
+
+    if let Some(event) = events.get(i) {
+      svg.push_str(&format!(
+        "    <title>{} - {}</title>\n",
+        format_time(get_clock_time(event.start)),
+        format_time(get_clock_time(event.end))
+      ));
+    }
+
+    svg.push_str(&format!(
+      "    <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#8fb8de\" fill-opacity=\"0.8\" stroke=\"#2f5d8a\"/>\n",
+      x,
+      y,
+      width,
+      tile_height
+    ));
+
+    if let Some(label) = labels.and_then(|labels| labels.get(i)) {
+      svg.push_str(&format!(
+        "    <text x=\"{}\" y=\"{}\">{}</text>\n",
+        x + LABEL_PADDING,
+        y + FONT_SIZE + LABEL_PADDING,
+        escape_xml(label)
+      ));
+    }
+
+    svg.push_str("  </g>\n");
+  }
+
+  svg.push_str("</svg>\n");
+
+  svg
+}
+
+/// Renders `tiles` as an SVG day view. `events` must be in the same order as
+/// `tiles`, and `labels`, when given, holds the text drawn inside each tile.
+pub fn render_svg(
+  tiles: &[tile::Tile],
+  events: &[event::Event],
+  labels: Option<&[String]>,
+  svg_options: &SvgOptions
+) -> String {
+  render(tiles, events, labels, svg_options, &|hours| hours)
+}
+
+/// Renders `tiles`, positioned on `day`, as an SVG day view like `render_svg`,
+/// but labels the grid and every event with the wall clock time on `day`. Set
+/// `svg_options.end_hour` to `day.get_hours()` to show the whole day.
+#[cfg(feature = "timezone")]
+pub fn render_day_svg(
+  tiles: &[tile::Tile],
+  events: &[event::Event],
+  labels: Option<&[String]>,
+  day: &time::DisplayDay,
+  svg_options: &SvgOptions
+) -> String {
+  render(tiles, events, labels, svg_options, &|position| day.get_wall_clock_time(position))
+}
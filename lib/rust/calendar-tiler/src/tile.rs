@@ -2,13 +2,24 @@ use std::fmt::{self, Formatter, Display};
 
 pub const X_SENTINEL: f64 = 0f64;
 pub const DX_SENTINEL: f64 = 1f64;
+pub const BASE_Z: u32 = 0;
 
 #[derive(PartialEq, PartialOrd, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tile {
   pub x: f64,
   pub y: f64,
   pub dx: f64,
   pub dy: f64,
+  /// Stacking order, higher tiles are drawn above lower ones. Methods that lay
+  /// tiles out side by side leave every tile at `BASE_Z`.
+  #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_base_z"))]
+  pub z: u32,
+}
+
+#[cfg(feature = "serde")]
+fn is_base_z(z: &u32) -> bool {
+  *z == BASE_Z
 }
 
 impl Display for Tile {
@@ -16,13 +27,14 @@ impl Display for Tile {
     &self,
     f: &mut Formatter
   ) -> fmt::Result {
-    write!(
+    writeln!(
       f,
-      "x: {:.3} dx: {:.3} y: {:.3} dy: {:.3}\n",
+      "x: {:.3} dx: {:.3} y: {:.3} dy: {:.3} z: {}",
       self.x,
       self.dx,
       self.y,
-      self.dy
+      self.dy,
+      self.z
     )
   }
 }
\ No newline at end of file
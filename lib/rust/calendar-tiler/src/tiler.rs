@@ -3,69 +3,157 @@ use crate::sorted_event;
 use crate::tile;
 use crate::tiling_method;
 use crate::tiler_options;
+use crate::tiler_error;
+use crate::layout_checker;
+use crate::layout_quality;
+use crate::overlap_cluster;
+use crate::all_day_layout;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::ops::Range;
 
-fn get_event_sorter(error_bound: f64) -> impl Fn(
-  &sorted_event::SortedEvent,
-  &sorted_event::SortedEvent
-) -> std::cmp::Ordering {
-  move |a: &sorted_event::SortedEvent, b: &sorted_event::SortedEvent| -> std::cmp::Ordering {
-    if a.event.start - b.event.start >= error_bound {
-      if a.event.start - b.event.start == error_bound {
-        if b.event.end - a.event.end >= error_bound {
-          std::cmp::Ordering::Greater
-        } else {
-          std::cmp::Ordering::Less
-        }
-      } else {
-        std::cmp::Ordering::Greater
-      }
-    } else {
-      std::cmp::Ordering::Less
+// Starts closer than `error_bound` count as the same start. Comparing them
+// pairwise is not transitive, so starts are binned into slots `error_bound`
+// wide instead, which keeps this a total order as `sort_by` requires.
+fn get_start_slot(
+  start: f64,
+  error_bound: f64
+) -> f64 {
+  if error_bound > 0.0 {
+    (start / error_bound).floor()
+  } else {
+    start
+  }
+}
+
+// Orders by start slot, then longest first, then by exact start.
+fn compare_sorted_events(
+  a: &sorted_event::SortedEvent,
+  b: &sorted_event::SortedEvent,
+  error_bound: f64
+) -> Ordering {
+  get_start_slot(a.event.start, error_bound).total_cmp(&get_start_slot(b.event.start, error_bound))
+                                            .then_with(|| b.event.end.total_cmp(&a.event.end))
+                                            .then_with(|| a.event.start.total_cmp(&b.event.start))
+}
+
+fn validate_event(
+  original_index: usize,
+  event: &event::Event
+) -> Result<(), tiler_error::TilerError> {
+  let reason = if !event.start.is_finite() {
+    Some(tiler_error::InvalidEventReason::NonFiniteStart)
+  } else if !event.end.is_finite() {
+    Some(tiler_error::InvalidEventReason::NonFiniteEnd)
+  } else if event.end < event.start {
+    Some(tiler_error::InvalidEventReason::EndBeforeStart)
+  } else {
+    None
+  };
+
+  match reason {
+    Some(reason) => Err(tiler_error::TilerError::InvalidEvent {
+      original_index,
+      reason
+    }),
+    None => Ok(())
+  }
+}
+
+/// Checks that every event has finite times and does not end before it
+/// starts, reporting the first one that fails.
+pub fn validate_events(
+  events: &[event::Event]
+) -> Result<(), tiler_error::TilerError> {
+  for (original_index, event) in events.iter().enumerate() {
+    validate_event(original_index, event)?;
+  }
+
+  Ok(())
+}
+
+fn new_sorted_event(
+  original_index: usize,
+  event: event::Event
+) -> sorted_event::SortedEvent {
+  sorted_event::SortedEvent {
+    event,
+    original_index,
+    sorted_index: 0,
+    tile: tile::Tile {
+      x: tile::X_SENTINEL,
+      dx: tile::DX_SENTINEL,
+      y: event.start,
+      dy: event.end - event.start,
+      z: tile::BASE_Z
     }
   }
 }
 
-fn sort_events(
-  events: &Vec<event::Event>,
+// The order `sort_events` produces, with ties spelled out so a single event
+// can be found or placed by binary search.
+fn compare_sorted_events_by_index(
+  a: &sorted_event::SortedEvent,
+  b: &sorted_event::SortedEvent,
+  error_bound: f64
+) -> Ordering {
+  compare_sorted_events(a, b, error_bound).then_with(|| a.original_index.cmp(&b.original_index))
+}
+
+/// Pairs every event with its tile and orders them by start, longest first
+/// among events that start within `error_bound` of each other.
+pub fn sort_events(
+  events: &[event::Event],
   tiler_options: &tiler_options::TilerOptions
 ) -> Vec<sorted_event::SortedEvent> {
-  let mut index: usize = 0;
-
-  let mut sorted_events: Vec<sorted_event::SortedEvent> = events.iter().map(|&e| {
-    let sorted_event = sorted_event::SortedEvent {
-      event: e,
-      original_index: index,
-      sorted_index: 0,
-      tile: tile::Tile {
-        x: tile::X_SENTINEL,
-        dx: tile::DX_SENTINEL,
-        y: e.start,
-        dy: e.end - e.start
-      }
-    };
+  let mut sorted_events: Vec<sorted_event::SortedEvent> = events.iter()
+                                                                .enumerate()
+                                                                .map(|(i, &e)| new_sorted_event(i, e))
+                                                                .collect();
 
-    index += 1;
+  sorted_events.sort_by(|a, b| compare_sorted_events(a, b, tiler_options.error_bound));
 
-    sorted_event
-  }).collect();
+  for (i, sorted_event) in sorted_events.iter_mut().enumerate() {
+    sorted_event.sorted_index = i;
+  }
 
-  let event_sorter = get_event_sorter(tiler_options.error_bound);
+  sorted_events
+}
+
+// The day grid only holds timed events, all-day events go to their lanes.
+fn sort_timed_events(
+  events: &[event::Event],
+  tiler_options: &tiler_options::TilerOptions
+) -> Vec<sorted_event::SortedEvent> {
+  let mut sorted_events = sort_events(events, tiler_options);
 
-  sorted_events.sort_by(|a, b| event_sorter(&a, &b));
+  sorted_events.retain(|sorted_event| !sorted_event.event.all_day);
 
-  for (i, mut sorted_event) in sorted_events.iter_mut().enumerate() {
+  for (i, sorted_event) in sorted_events.iter_mut().enumerate() {
     sorted_event.sorted_index = i;
   }
 
   sorted_events
 }
 
+fn get_range_end(
+  sorted_events: &[sorted_event::SortedEvent]
+) -> f64 {
+  sorted_events.iter().map(|sorted_event| sorted_event.event.end).fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Tiles the timed events of a day. `sorted_events` and `tiles` only hold
+/// timed events, while all-day events are stacked in `all_day_tiles`.
 pub struct Tiler<T: tiling_method::TilingMethod> {
   pub tiles: Vec<tile::Tile>,
   pub tiling_method: T,
   pub events: Vec<event::Event>,
   pub sorted_events: Vec<sorted_event::SortedEvent>,
   pub tiler_options: tiler_options::TilerOptions,
+  pub all_day_tiles: Vec<all_day_layout::AllDayTile>,
+  /// Id of the event at each original index.
+  pub event_ids: Vec<event::EventId>,
+  next_event_id: u64,
 }
 
 impl<T: tiling_method::TilingMethod> Tiler<T> {
@@ -73,25 +161,294 @@ impl<T: tiling_method::TilingMethod> Tiler<T> {
     events: Vec<event::Event>,
     tiler_options: tiler_options::TilerOptions,
     tiling_method: T
-  ) -> Tiler<T> {
-    let sorted_events = self::sort_events(&events, &tiler_options);
+  ) -> Result<Tiler<T>, tiler_error::TilerError> {
+    self::validate_events(&events)?;
+
+    let sorted_events = self::sort_timed_events(&events, &tiler_options);
     let tiles: Vec<tile::Tile> = sorted_events.iter().map(|e| e.tile).collect();
+    let all_day_tiles = all_day_layout::layout_all_day_events(&events, &tiler_options);
+    let event_ids: Vec<event::EventId> = (0 .. events.len() as u64).map(event::EventId).collect();
 
-    Tiler::<T> {
+    Ok(Tiler::<T> {
       tiler_options,
-      events: events,
+      next_event_id: events.len() as u64,
+      events,
       sorted_events,
-      tiles: tiles,
+      tiles,
       tiling_method,
-    }
+      all_day_tiles,
+      event_ids,
+    })
   }
 
-  pub fn tile(&mut self) {
+  pub fn tile(&mut self) -> Result<(), tiler_error::TilerError> {
     self.tiling_method.tile(
       &mut self.tiles,
       &self.sorted_events,
       &self.tiler_options
-    );
+    )
+  }
+
+  /// Tiles the day, and returns the tiles of its timed events together with
+  /// the lanes of its all-day events.
+  pub fn tile_day(&mut self) -> Result<all_day_layout::DayLayout, tiler_error::TilerError> {
+    self.tile()?;
+
+    let mut tiles: Vec<(usize, tile::Tile)> = self.sorted_events.iter().map(|sorted_event| {
+      (sorted_event.original_index, self.tiles[sorted_event.sorted_index])
+    }).collect();
+
+    tiles.sort_by_key(|&(original_index, _)| original_index);
+
+    Ok(all_day_layout::DayLayout {
+      tiles,
+      all_day_tiles: self.all_day_tiles.clone()
+    })
+  }
+
+  /// Original index of the event with `event_id`, if it is still here.
+  pub fn get_original_index(
+    &self,
+    event_id: event::EventId
+  ) -> Option<usize> {
+    self.event_ids.iter().position(|&other| other == event_id)
+  }
+
+  /// Adds `event` after the existing events and re-tiles the clusters it
+  /// touches. Returns the new event's id, and every tile whose geometry
+  /// changed, keyed by event id, including the new event's tile. An all-day
+  /// event changes no tiles and is only added to `all_day_tiles`.
+  pub fn insert_event(
+    &mut self,
+    event: event::Event
+  ) -> Result<(event::EventId, Vec<(event::EventId, tile::Tile)>), tiler_error::TilerError> {
+    let original_index = self.events.len();
+    let event_id = event::EventId(self.next_event_id);
+
+    validate_event(original_index, &event)?;
+
+    self.events.push(event);
+    self.event_ids.push(event_id);
+    self.next_event_id += 1;
+
+    if event.all_day {
+      self.all_day_tiles = all_day_layout::layout_all_day_events(&self.events, &self.tiler_options);
+
+      return Ok((event_id, vec![]));
+    }
+
+    self.insert_sorted_event(original_index, event);
+
+    Ok((event_id, self.retile_around(Some(original_index), &[event])?))
+  }
+
+  /// Moves the event with `event_id` to `event` and re-tiles the clusters it
+  /// left and joined. Returns every tile whose geometry changed, including
+  /// the moved event's tile.
+  pub fn update_event(
+    &mut self,
+    event_id: event::EventId,
+    event: event::Event
+  ) -> Result<Vec<(event::EventId, tile::Tile)>, tiler_error::TilerError> {
+    let original_index = self.get_known_original_index(event_id)?;
+    let previous_event = self.events[original_index];
+
+    validate_event(original_index, &event)?;
+
+    self.events[original_index] = event;
+
+    if !previous_event.all_day {
+      self.remove_sorted_event(original_index, previous_event);
+    }
+
+    if !event.all_day {
+      self.insert_sorted_event(original_index, event);
+    }
+
+    if previous_event.all_day || event.all_day {
+      self.all_day_tiles = all_day_layout::layout_all_day_events(&self.events, &self.tiler_options);
+    }
+
+    let timed_events: Vec<event::Event> = [previous_event, event].iter().copied().filter(|e| !e.all_day).collect();
+
+    self.retile_around(Some(original_index), &timed_events)
+  }
+
+  /// Removes the event with `event_id`, shifting the original indices of
+  /// later events down one as `Vec::remove` does, and re-tiles the clusters
+  /// it left. Returns every remaining tile whose geometry changed.
+  pub fn remove_event(
+    &mut self,
+    event_id: event::EventId
+  ) -> Result<Vec<(event::EventId, tile::Tile)>, tiler_error::TilerError> {
+    let original_index = self.get_known_original_index(event_id)?;
+    let previous_event = self.events[original_index];
+
+    self.events.remove(original_index);
+    self.event_ids.remove(original_index);
+
+    if !previous_event.all_day {
+      self.remove_sorted_event(original_index, previous_event);
+    }
+
+    for sorted_event in self.sorted_events.iter_mut() {
+      if sorted_event.original_index > original_index {
+        sorted_event.original_index -= 1;
+      }
+    }
+
+    if !self.all_day_tiles.is_empty() {
+      self.all_day_tiles = all_day_layout::layout_all_day_events(&self.events, &self.tiler_options);
+    }
+
+    if previous_event.all_day {
+      return Ok(vec![]);
+    }
+
+    self.retile_around(None, &[previous_event])
+  }
+
+  fn get_known_original_index(
+    &self,
+    event_id: event::EventId
+  ) -> Result<usize, tiler_error::TilerError> {
+    self.get_original_index(event_id).ok_or(tiler_error::TilerError::UnknownEvent { event_id })
+  }
+
+  fn reindex_sorted_events(
+    &mut self,
+    from_sorted_index: usize
+  ) {
+    for (i, sorted_event) in self.sorted_events.iter_mut().enumerate().skip(from_sorted_index) {
+      sorted_event.sorted_index = i;
+    }
+  }
+
+  fn insert_sorted_event(
+    &mut self,
+    original_index: usize,
+    event: event::Event
+  ) {
+    let sorted_event = new_sorted_event(original_index, event);
+    let sorted_index = self.sorted_events.partition_point(|other| {
+      compare_sorted_events_by_index(other, &sorted_event, self.tiler_options.error_bound) == Ordering::Less
+    });
+
+    self.sorted_events.insert(sorted_index, sorted_event);
+    self.tiles.insert(sorted_index, sorted_event.tile);
+    self.reindex_sorted_events(sorted_index);
+  }
+
+  fn remove_sorted_event(
+    &mut self,
+    original_index: usize,
+    event: event::Event
+  ) {
+    let key = new_sorted_event(original_index, event);
+
+    if let Ok(sorted_index) = self.sorted_events.binary_search_by(|other| {
+      compare_sorted_events_by_index(other, &key, self.tiler_options.error_bound)
+    }) {
+      self.sorted_events.remove(sorted_index);
+      self.tiles.remove(sorted_index);
+      self.reindex_sorted_events(sorted_index);
+    }
+  }
+
+  fn retile_around(
+    &mut self,
+    changed_original_index: Option<usize>,
+    changed_events: &[event::Event]
+  ) -> Result<Vec<(event::EventId, tile::Tile)>, tiler_error::TilerError> {
+    let ranges: Vec<Range<usize>> = if self.tiling_method.tiles_clusters_independently() {
+      self.get_retile_ranges().into_iter().filter(|range| {
+        let range_start = self.sorted_events[range.start].event.start;
+        let range_end = get_range_end(&self.sorted_events[range.clone()]);
+
+        changed_events.iter().any(|event| event.start <= range_end && range_start <= event.end)
+      }).collect()
+    } else {
+      vec![Range { start: 0, end: self.sorted_events.len() }]
+    };
+
+    let mut changed_tiles: Vec<(event::EventId, tile::Tile)> = vec![];
+
+    for range in ranges {
+      let previous_tiles: Vec<tile::Tile> = self.tiles[range.clone()].to_vec();
+      let cluster: Vec<sorted_event::SortedEvent> = self.sorted_events[range.clone()].iter().map(|sorted_event| {
+        sorted_event::SortedEvent {
+          sorted_index: sorted_event.sorted_index - range.start,
+          ..*sorted_event
+        }
+      }).collect();
+
+      self.tiling_method.tile(&mut self.tiles[range.clone()], &cluster, &self.tiler_options)?;
+
+      for (sorted_event, previous_tile) in self.sorted_events[range.clone()].iter().zip(previous_tiles.iter()) {
+        let tile = self.tiles[sorted_event.sorted_index];
+
+        if Some(sorted_event.original_index) == changed_original_index || tile != *previous_tile {
+          changed_tiles.push((self.event_ids[sorted_event.original_index], tile));
+        }
+      }
+    }
+
+    changed_tiles.sort_by_key(|&(event_id, _)| event_id);
+
+    Ok(changed_tiles)
+  }
+
+  /// Overlap clusters, merged wherever one starts within `error_bound` of
+  /// where the last one ended. `generate_columns` keeps such touching events
+  /// out of each other's columns and the space-filling DAG links them, so
+  /// only the remaining boundaries separate parts of the day that can be
+  /// tiled one by one with the same result as tiling it whole.
+  fn get_retile_ranges(&self) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+
+    for range in overlap_cluster::get_cluster_ranges(&self.sorted_events, &self.tiler_options) {
+      match ranges.last_mut() {
+        Some(last_range) if self.sorted_events[range.start].event.start
+          - get_range_end(&self.sorted_events[last_range.clone()]) < self.tiler_options.error_bound => {
+          last_range.end = range.end;
+        },
+        _ => ranges.push(range)
+      }
+    }
+
+    ranges
+  }
+
+  pub fn get_overlap_clusters(&self) -> Vec<overlap_cluster::OverlapCluster> {
+    overlap_cluster::find_overlap_clusters(&self.sorted_events, &self.tiler_options)
+  }
+
+  /// One tile per event. All-day events keep a full-width tile over their
+  /// span, since they are placed by `all_day_tiles` instead.
+  pub fn get_tiles_in_original_order(&self) -> Vec<tile::Tile> {
+    let mut tiles: Vec<tile::Tile> = self.events.iter().map(|&event| new_sorted_event(0, event).tile).collect();
+
+    for sorted_event in self.sorted_events.iter() {
+      tiles[sorted_event.original_index] = self.tiles[sorted_event.sorted_index];
+    }
+
+    tiles
+  }
+
+  pub fn check_layout(&self) -> Vec<layout_checker::LayoutViolation> {
+    layout_checker::check_layout(
+      &self.get_tiles_in_original_order(),
+      &self.events,
+      &self.tiler_options
+    )
+  }
+
+  pub fn score_layout(&self) -> layout_quality::LayoutQuality {
+    layout_quality::score_layout(
+      &self.get_tiles_in_original_order(),
+      &self.events,
+      &self.tiler_options
+    )
   }
 
   pub fn print_tiles(&self) {
@@ -101,41 +458,77 @@ impl<T: tiling_method::TilingMethod> Tiler<T> {
   }
 }
 
-fn get_event_column_index(
-  columns: &Vec<Vec<sorted_event::SortedEvent>>,
-  sorted_event: &sorted_event::SortedEvent,
-  tiler_options: &tiler_options::TilerOptions
-) -> Option<usize> {
-  let mut column: &Vec<sorted_event::SortedEvent>;
+struct ColumnEnd {
+  end: f64,
+  column_index: usize,
+}
 
-  for i in 0 .. columns.len() {
-    column = &columns[i];
+impl PartialEq for ColumnEnd {
+  fn eq(
+    &self,
+    other: &ColumnEnd
+  ) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
 
-    if sorted_event.event.start - column[column.len() - 1].event.end >= tiler_options.error_bound {
-      return Some(i);
-    }
+impl Eq for ColumnEnd {}
+
+impl Ord for ColumnEnd {
+  fn cmp(
+    &self,
+    other: &ColumnEnd
+  ) -> Ordering {
+    self.end.total_cmp(&other.end)
+            .then_with(|| self.column_index.cmp(&other.column_index))
   }
-  
-  None
 }
 
+impl PartialOrd for ColumnEnd {
+  fn partial_cmp(
+    &self,
+    other: &ColumnEnd
+  ) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+/// Places every event in the leftmost column that is free by its start, in
+/// O(n log n). A column frees up `error_bound` after its last event ends, and
+/// since events arrive by start, it stays free once freed.
 pub fn generate_columns(
-  sorted_events: &Vec<sorted_event::SortedEvent>,
+  sorted_events: &[sorted_event::SortedEvent],
   tiler_options: &tiler_options::TilerOptions
 ) -> Vec<Vec<sorted_event::SortedEvent>> {
-  let mut columns: Vec<Vec<sorted_event::SortedEvent>> = vec![vec![sorted_events[0]]];
-
-  for i in 0 .. sorted_events.len() {
-    if let Some(column_index) = get_event_column_index(
-      &columns,
-      &sorted_events[i],
-      tiler_options
-    ) {
-      columns[column_index].push(sorted_events[i]);
-    } else {
-      columns.push(vec![sorted_events[i]]);
+  let mut columns: Vec<Vec<sorted_event::SortedEvent>> = vec![];
+  let mut column_ends: BinaryHeap<Reverse<ColumnEnd>> = BinaryHeap::new();
+  let mut free_columns: BinaryHeap<Reverse<usize>> = BinaryHeap::new();
+
+  for sorted_event in sorted_events.iter() {
+    while let Some(Reverse(column_end)) = column_ends.peek() {
+      if sorted_event.event.start - column_end.end < tiler_options.error_bound {
+        break;
+      }
+
+      free_columns.push(Reverse(column_end.column_index));
+      column_ends.pop();
     }
+
+    let column_index = match free_columns.pop() {
+      Some(Reverse(column_index)) => column_index,
+      None => {
+        columns.push(vec![]);
+        columns.len() - 1
+      }
+    };
+
+    columns[column_index].push(*sorted_event);
+
+    column_ends.push(Reverse(ColumnEnd {
+      end: sorted_event.event.end,
+      column_index
+    }));
   }
-            
+
   columns
 }
@@ -0,0 +1,103 @@
+use std::fmt::{self, Formatter, Display};
+use crate::event;
+use crate::optimized_tiling_method;
+use crate::simplex;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum InvalidEventReason {
+  NonFiniteStart,
+  NonFiniteEnd,
+  EndBeforeStart,
+}
+
+/// Why the optimized tiling method laid a cluster out without solving it.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum FallbackReason {
+  ClusterTooLarge,
+  SolverFailed(simplex::SimplexError),
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum TilerError {
+  InvalidEvent {
+    original_index: usize,
+    reason: InvalidEventReason,
+  },
+  UnknownEvent {
+    event_id: event::EventId,
+  },
+  UnsatisfiableMinWidth {
+    min_width: f64,
+    max_min_width: f64,
+  },
+  OptimizationFallback {
+    sorted_index: usize,
+    number_of_events: usize,
+    reason: FallbackReason,
+  },
+}
+
+impl Display for InvalidEventReason {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    match self {
+      InvalidEventReason::NonFiniteStart => write!(f, "start is not a finite number"),
+      InvalidEventReason::NonFiniteEnd => write!(f, "end is not a finite number"),
+      InvalidEventReason::EndBeforeStart => write!(f, "end is before start"),
+    }
+  }
+}
+
+impl Display for FallbackReason {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    match self {
+      FallbackReason::ClusterTooLarge => write!(
+        f,
+        "it has more than {} events",
+        optimized_tiling_method::MAX_OPTIMIZED_CLUSTER_SIZE
+      ),
+      FallbackReason::SolverFailed(simplex_error) => write!(f, "the solver failed, {}", simplex_error),
+    }
+  }
+}
+
+impl Display for TilerError {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    match self {
+      TilerError::InvalidEvent { original_index, reason } => write!(
+        f,
+        "event at index {} is invalid: {}",
+        original_index,
+        reason
+      ),
+      TilerError::UnknownEvent { event_id } => write!(
+        f,
+        "there is no event with id {}",
+        event_id
+      ),
+      TilerError::UnsatisfiableMinWidth { min_width, max_min_width } => write!(
+        f,
+        "tiles cannot all be {} wide, overlapping events leave room for at most {}",
+        min_width,
+        max_min_width
+      ),
+      TilerError::OptimizationFallback { sorted_index, number_of_events, reason } => write!(
+        f,
+        "the cluster of {} events from sorted index {} was not optimized: {}",
+        number_of_events,
+        sorted_index,
+        reason
+      ),
+    }
+  }
+}
+
+impl std::error::Error for TilerError {}
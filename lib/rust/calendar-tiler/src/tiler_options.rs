@@ -1,12 +1,82 @@
+use wasm_bindgen::prelude::*;
+
 const DEFAULT_ERROR_BOUND: f64 = 0.0000001;
+const DEFAULT_CASCADE_INDENT_STEP: f64 = 0.1;
+const DEFAULT_CASCADE_MAX_INDENT: f64 = 0.5;
+const DEFAULT_MIN_WIDTH: f64 = 0.0;
+const DEFAULT_READABLE_WIDTH: f64 = 0.2;
 
+#[wasm_bindgen]
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SerializedTilerOptions"))]
 pub struct TilerOptions {
   pub error_bound: f64,
   pub number_of_decimal_places: u32,
   pub rounding_modifier: u128,
+  /// Fraction of the width each deeper cascaded event is indented by.
+  pub cascade_indent_step: f64,
+  /// Fraction of the width no cascaded event is indented past.
+  pub cascade_max_indent: f64,
+  /// Fraction of the width the optimized tiling method gives every tile at least.
+  pub min_width: f64,
+  /// Fraction of the width below which layout scoring counts a tile as too
+  /// narrow to read.
+  pub readable_width: f64,
+}
+
+#[cfg(feature = "serde")]
+fn default_cascade_indent_step() -> f64 {
+  DEFAULT_CASCADE_INDENT_STEP
+}
+
+#[cfg(feature = "serde")]
+fn default_cascade_max_indent() -> f64 {
+  DEFAULT_CASCADE_MAX_INDENT
+}
+
+#[cfg(feature = "serde")]
+fn default_min_width() -> f64 {
+  DEFAULT_MIN_WIDTH
+}
+
+#[cfg(feature = "serde")]
+fn default_readable_width() -> f64 {
+  DEFAULT_READABLE_WIDTH
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedTilerOptions {
+  error_bound: f64,
+  #[serde(default = "default_cascade_indent_step")]
+  cascade_indent_step: f64,
+  #[serde(default = "default_cascade_max_indent")]
+  cascade_max_indent: f64,
+  #[serde(default = "default_min_width")]
+  min_width: f64,
+  #[serde(default = "default_readable_width")]
+  readable_width: f64,
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedTilerOptions> for TilerOptions {
+  fn from(
+    serialized_tiler_options: SerializedTilerOptions
+  ) -> TilerOptions {
+    TilerOptions {
+      cascade_indent_step: serialized_tiler_options.cascade_indent_step,
+      cascade_max_indent: serialized_tiler_options.cascade_max_indent,
+      min_width: serialized_tiler_options.min_width,
+      readable_width: serialized_tiler_options.readable_width,
+      ..TilerOptions::new(serialized_tiler_options.error_bound)
+    }
+  }
 }
 
+#[wasm_bindgen]
 impl TilerOptions {
+  #[wasm_bindgen(constructor)]
   pub fn new(error_bound: f64) -> TilerOptions {
     let sanitized_error_bound = if error_bound < 0.0 {
       error_bound.abs()
@@ -38,6 +108,10 @@ impl TilerOptions {
       error_bound: sanitized_error_bound,
       number_of_decimal_places,
       rounding_modifier,
+      cascade_indent_step: DEFAULT_CASCADE_INDENT_STEP,
+      cascade_max_indent: DEFAULT_CASCADE_MAX_INDENT,
+      min_width: DEFAULT_MIN_WIDTH,
+      readable_width: DEFAULT_READABLE_WIDTH,
     }
   }
 }
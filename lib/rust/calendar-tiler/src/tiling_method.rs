@@ -1,49 +1,199 @@
 use crate::tile;
 use crate::sorted_event;
 use crate::tiler_options;
+use crate::tiler_error;
+use crate::basic_tiling_method;
+use crate::space_filling_tiling_method;
+use crate::time_respective_tiling_method;
+use crate::interval_coloring_tiling_method;
+use crate::expand_right_tiling_method;
+use crate::cascade_tiling_method;
+use crate::optimized_tiling_method;
+use crate::auto_tiling_method;
+use crate::layout_quality;
+use std::fmt::{self, Formatter, Display};
 use std::str::FromStr;
+use wasm_bindgen::prelude::*;
 
 pub trait TilingMethod {
   fn tile(
     &self,
-    tiles: &mut Vec<tile::Tile>,
-    sorted_events: &Vec<sorted_event::SortedEvent>,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
     tiler_options: &tiler_options::TilerOptions,
-  );
+  ) -> Result<(), tiler_error::TilerError>;
+
+  /// Whether tiling every overlap cluster on its own gives the same tiles as
+  /// tiling the whole day, which lets `Tiler` re-tile edits incrementally.
+  fn tiles_clusters_independently(&self) -> bool {
+    true
+  }
 }
 
+#[wasm_bindgen]
+#[derive(PartialEq, Copy, Clone, Debug)]
 pub enum TilingMethodName {
   Basic = 1,
   SpaceFilling = 2,
-  TimeRespective = 3
+  TimeRespective = 3,
+  IntervalColoring = 4,
+  ExpandRight = 5,
+  Cascade = 6,
+  Optimized = 7,
+  Auto = 8
+}
+
+impl TilingMethodName {
+  pub const ALL: [TilingMethodName; 8] = [
+    TilingMethodName::Basic,
+    TilingMethodName::SpaceFilling,
+    TilingMethodName::TimeRespective,
+    TilingMethodName::IntervalColoring,
+    TilingMethodName::ExpandRight,
+    TilingMethodName::Cascade,
+    TilingMethodName::Optimized,
+    TilingMethodName::Auto
+  ];
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      TilingMethodName::Basic => "basic",
+      TilingMethodName::SpaceFilling => "space-filling",
+      TilingMethodName::TimeRespective => "time-respective",
+      TilingMethodName::IntervalColoring => "interval-coloring",
+      TilingMethodName::ExpandRight => "expand-right",
+      TilingMethodName::Cascade => "cascade",
+      TilingMethodName::Optimized => "optimized",
+      TilingMethodName::Auto => "auto"
+    }
+  }
+
+  pub fn description(&self) -> &'static str {
+    match self {
+      TilingMethodName::Basic => {
+        "Packs events into first-fit columns and gives every column an equal share of the day's width."
+      },
+      TilingMethodName::SpaceFilling => {
+        "Starts from first-fit columns and widens events along their longest chain of overlaps to fill free space."
+      },
+      TilingMethodName::TimeRespective => {
+        "Orders overlapping events left to right by start time and widens them along their chain of overlaps."
+      },
+      TilingMethodName::IntervalColoring => {
+        "Colors each overlap cluster with as few columns as it needs and gives them an equal share of the width."
+      },
+      TilingMethodName::ExpandRight => {
+        "Colors each overlap cluster into columns, then widens every event over the free columns to its right."
+      },
+      TilingMethodName::Cascade => {
+        "Stacks overlapping events on top of each other, indenting each conflicting event a little further."
+      },
+      TilingMethodName::Optimized => {
+        "Solves each overlap cluster for the widest minimum width, then for the most area covered. Clusters over 64 events only get the widest minimum width."
+      },
+      TilingMethodName::Auto => {
+        "Tiles each overlap cluster with every side by side method and keeps the layout that scores best."
+      }
+    }
+  }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct ParseTilingMethodNameError(pub String);
+
+impl Display for ParseTilingMethodNameError {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    write!(f, "unknown tiling method: {}", self.0)
+  }
+}
+
+impl std::error::Error for ParseTilingMethodNameError {}
+
+impl Display for TilingMethodName {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
 }
 
 impl<T: ?Sized> TilingMethod for Box<T> where T: TilingMethod {
   fn tile(
     &self,
-    tiles: &mut Vec<tile::Tile>,
-    sorted_events: &Vec<sorted_event::SortedEvent>,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
     tiler_options: &tiler_options::TilerOptions
-  ) {
+  ) -> Result<(), tiler_error::TilerError> {
     (**self).tile(
       tiles,
       sorted_events,
       tiler_options
-    );
+    )
+  }
+
+  fn tiles_clusters_independently(&self) -> bool {
+    (**self).tiles_clusters_independently()
   }
 }
 
 impl FromStr for TilingMethodName {
-  type Err = ();
+  type Err = ParseTilingMethodNameError;
 
   fn from_str(
     s: &str
   ) -> Result<TilingMethodName, Self::Err> {
-    match s {
-      "1" => Ok(TilingMethodName::Basic),
-      "2" => Ok(TilingMethodName::SpaceFilling),
-      "3" => Ok(TilingMethodName::TimeRespective),
-      _ => Err(())
+    TilingMethodName::ALL.iter()
+                         .find(|tiling_method_name| tiling_method_name.name() == s)
+                         .copied()
+                         .ok_or_else(|| ParseTilingMethodNameError(s.to_string()))
+  }
+}
+
+pub fn get_tiling_method(
+  tiling_method_name: TilingMethodName
+) -> Box<dyn TilingMethod> {
+  match tiling_method_name {
+    TilingMethodName::Basic => {
+      Box::new(basic_tiling_method::BasicTilingMethod::new())
+    },
+    TilingMethodName::SpaceFilling => {
+      Box::new(space_filling_tiling_method::SpaceFillingTilingMethod::new())
+    },
+    TilingMethodName::TimeRespective => {
+      Box::new(time_respective_tiling_method::TimeRespectiveTilingMethod::new())
+    },
+    TilingMethodName::IntervalColoring => {
+      Box::new(interval_coloring_tiling_method::IntervalColoringTilingMethod::new())
+    },
+    TilingMethodName::ExpandRight => {
+      Box::new(expand_right_tiling_method::ExpandRightTilingMethod::new())
+    },
+    TilingMethodName::Cascade => {
+      Box::new(cascade_tiling_method::CascadeTilingMethod::new())
+    },
+    TilingMethodName::Optimized => {
+      Box::new(optimized_tiling_method::OptimizedTilingMethod::new())
+    },
+    TilingMethodName::Auto => {
+      Box::new(auto_tiling_method::AutoTilingMethod::new())
     }
   }
 }
+
+/// Like `get_tiling_method`, with `TilingMethodName::Auto` ranking layouts by
+/// `objective`. Other methods do not score layouts, so they ignore it.
+pub fn get_tiling_method_with_objective(
+  tiling_method_name: TilingMethodName,
+  objective: layout_quality::LayoutObjective
+) -> Box<dyn TilingMethod> {
+  match tiling_method_name {
+    TilingMethodName::Auto => {
+      Box::new(auto_tiling_method::AutoTilingMethod::with_objective(objective))
+    },
+    _ => get_tiling_method(tiling_method_name)
+  }
+}
@@ -0,0 +1,114 @@
+use crate::event;
+use crate::week_tiler;
+use jiff::{civil, tz, Timestamp};
+use std::fmt::{self, Formatter, Display};
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum TimeError {
+  UnknownTimeZone {
+    name: String,
+  },
+  InvalidDate {
+    date: String,
+  },
+}
+
+impl Display for TimeError {
+  fn fmt(
+    &self,
+    f: &mut Formatter
+  ) -> fmt::Result {
+    match self {
+      TimeError::UnknownTimeZone { name } => write!(f, "there is no time zone named {:?}", name),
+      TimeError::InvalidDate { date } => write!(f, "{:?} is not a supported YYYY-MM-DD date", date),
+    }
+  }
+}
+
+impl std::error::Error for TimeError {}
+
+/// One calendar day as a wall clock in an IANA time zone shows it, running
+/// from one local midnight to the next. Days a DST transition falls on are 23
+/// or 25 hours long.
+///
+/// Positions on the day are hours elapsed since its start, so every instant
+/// of the day has exactly one position, even when the wall clock skips or
+/// repeats an hour. Hand the events from `localize_events` to `Tiler` as they
+/// are, and label the day grid with `get_wall_clock_time`.
+#[derive(Clone, Debug)]
+pub struct DisplayDay {
+  /// The local midnight starting the day, in seconds since the Unix epoch.
+  pub start: f64,
+  /// The local midnight starting the next day, in seconds since the Unix
+  /// epoch.
+  pub end: f64,
+  time_zone: tz::TimeZone,
+}
+
+impl DisplayDay {
+  /// Looks `time_zone_name` up in the bundled tz database. When the wall clock
+  /// skips midnight, the day starts at the first instant after the gap.
+  pub fn new(
+    date: &str,
+    time_zone_name: &str
+  ) -> Result<DisplayDay, TimeError> {
+    let time_zone = tz::TimeZone::get(time_zone_name).map_err(|_| TimeError::UnknownTimeZone {
+      name: time_zone_name.to_string()
+    })?;
+    let invalid_date = |_| TimeError::InvalidDate { date: date.to_string() };
+    let civil_date: civil::Date = date.parse().map_err(invalid_date)?;
+    let start = civil_date.to_zoned(time_zone.clone()).map_err(invalid_date)?;
+    let end = civil_date.tomorrow().and_then(|tomorrow| tomorrow.to_zoned(time_zone.clone())).map_err(invalid_date)?;
+
+    Ok(DisplayDay {
+      start: start.timestamp().as_second() as f64,
+      end: end.timestamp().as_second() as f64,
+      time_zone,
+    })
+  }
+
+  pub fn get_time_zone(&self) -> &tz::TimeZone {
+    &self.time_zone
+  }
+
+  pub fn get_hours(&self) -> f64 {
+    (self.end - self.start) / week_tiler::SECONDS_PER_HOUR
+  }
+
+  /// Position of `instant`, in seconds since the Unix epoch, on the day.
+  pub fn get_position(
+    &self,
+    instant: f64
+  ) -> f64 {
+    (instant - self.start) / week_tiler::SECONDS_PER_HOUR
+  }
+
+  /// Hours since midnight the wall clock reads at `position`, which is first
+  /// clamped to the day. The end of the day reads 0.
+  pub fn get_wall_clock_time(
+    &self,
+    position: f64
+  ) -> f64 {
+    let instant = self.start + position.clamp(0.0, self.get_hours()) * week_tiler::SECONDS_PER_HOUR;
+    // Both ends of the day are valid timestamps, so everything between them is too.
+    let timestamp = Timestamp::from_second(instant.floor() as i64).unwrap();
+    let local_seconds = instant + self.time_zone.to_offset(timestamp).seconds() as f64;
+
+    local_seconds.rem_euclid(week_tiler::SECONDS_PER_DAY) / week_tiler::SECONDS_PER_HOUR
+  }
+
+  /// Cuts the parts of `events`, timed in seconds since the Unix epoch, that
+  /// fall on the day out of them, with the same rules as
+  /// `week_tiler::split_into_days`, and returns them in order of original
+  /// index, timed as positions on the day.
+  pub fn localize_events(
+    &self,
+    events: &[event::Event]
+  ) -> Vec<week_tiler::DaySegment> {
+    events.iter().enumerate().filter(|(_, event)| {
+      week_tiler::covers_range(event, self.start, self.end)
+    }).map(|(original_index, event)| {
+      week_tiler::cut_day_segment(original_index, event, 0, self.start, self.end)
+    }).collect()
+  }
+}
@@ -2,9 +2,11 @@ use crate::sorted_event;
 use crate::tile;
 use crate::tiling_method;
 use crate::tiler_options;
+use crate::tiler_error;
 use crate::dag_builder;
 
-struct Alignment {
+#[doc(hidden)]
+pub struct Alignment {
   front: Vec<Vec<usize>>,
   back: Vec<Vec<usize>>,
   reduced_back: Vec<Option<Vec<usize>>>,
@@ -26,10 +28,10 @@ impl Alignment {
 
 fn get_tail(
   head: usize,
-  array: &Vec<Option<Vec<usize>>>
+  array: &[Option<Vec<usize>>]
 ) -> usize {
-  for i in (head + 1) .. array.len() {
-    if array[i] == None {
+  for (i, item) in array.iter().enumerate().skip(head + 1) {
+    if item.is_none() {
       return i;
     }
   }
@@ -45,12 +47,18 @@ fn expand_reduced_front(
     return
   }
 
-  let mut next = alignment.reduced_front[alignment.reduced_front[front_vertex][0]][0];
+  let mut next = match alignment.reduced_front[front_vertex].first() {
+    Some(&reduced_front_vertex) => match alignment.reduced_front[reduced_front_vertex].first() {
+      Some(&next) => next,
+      None => return
+    },
+    None => return
+  };
 
   while next > 0 {
     alignment.reduced_front[front_vertex].push(next);
 
-    if alignment.reduced_front[next].len() > 0 {
+    if !alignment.reduced_front[next].is_empty() {
       next = alignment.reduced_front[next][0];
     } else {
       return
@@ -65,10 +73,8 @@ fn shares_linchpin(
 ) -> bool {
   match &alignment.reduced_back[mid_front] {
     Some(reduced_back_vector) => {
-      for i in (0 .. reduced_back_vector.len()).rev() {
-        let linchpin = reduced_back_vector[i];
-    
-        if alignment.reduced_front[front_vertex].iter().any(|a| *a == linchpin) {
+      for linchpin in reduced_back_vector.iter().rev() {
+        if alignment.reduced_front[front_vertex].contains(linchpin) {
           return true;
         }
       }
@@ -85,12 +91,8 @@ fn has_front_collision(
   mid_fron_usize: usize
 ) -> bool {
   back != mid_fron_usize
-    && (alignment.reduced_front[back].iter().any(
-        |a| *a == mid_fron_usize) || shares_linchpin(
-                                                      alignment,
-                                                      mid_fron_usize,
-                                                      back
-                                                    ))
+    && (alignment.reduced_front[back].contains(&mid_fron_usize)
+        || shares_linchpin(alignment, mid_fron_usize, back))
 }
 
 fn find_next_reduced_front_from_back(
@@ -107,7 +109,7 @@ fn find_next_reduced_front_from_back(
   for i in 0 .. alignment.back[front_vertex].len() {
     let back = alignment.back[front_vertex][i];
 
-    if reduced_back_front_vertex.iter().any(|a| *a == back) {
+    if reduced_back_front_vertex.contains(&back) {
       expand_reduced_front(alignment, back);
 
       if let Some(mid_fron_usize) = mid_front {
@@ -133,8 +135,8 @@ fn build_reduced_front(
     if let Some(reduced_back_i) = &alignment.reduced_back[i] {
       if alignment.back[i].len() == reduced_back_i.len() {
         let reduced_back_front_i_0_length = if let Some(
-          reduced_back_front_i_0
-        ) = &alignment.reduced_back[alignment.front[i][0]] {
+          Some(reduced_back_front_i_0)
+        ) = alignment.front[i].first().map(|&front_i_0| &alignment.reduced_back[front_i_0]) {
           reduced_back_front_i_0.len()
         } else {
           0
@@ -142,7 +144,7 @@ fn build_reduced_front(
         
         reduced_back_is_back = true;
 
-        if alignment.front[i].len() > 0
+        if !alignment.front[i].is_empty()
             && reduced_back_front_i_0_length > reduced_back_i.len() {
           alignment.reduced_front[i].push(alignment.front[i][0]);
         }
@@ -152,7 +154,7 @@ fn build_reduced_front(
     if !reduced_back_is_back {
       let mut should_add_next = false;
 
-      if next == None {
+      if next.is_none() {
         if let Some(next_reduced_front_usize) = find_next_reduced_front_from_back(
           alignment,
           i
@@ -161,13 +163,13 @@ fn build_reduced_front(
         }
       }
 
-      let is_next_present_in_back = if let Some (next_usize) = next {
-        alignment.back[alignment.front[i][0]].iter().any(|a| *a == next_usize)
+      let is_next_present_in_back = if let (Some(next_usize), Some(&front_i_0)) = (next, alignment.front[i].first()) {
+        alignment.back[front_i_0].contains(&next_usize)
       } else {
         false
       };
 
-      if alignment.front[i].len() > 0 && is_next_present_in_back {
+      if !alignment.front[i].is_empty() && is_next_present_in_back {
         if let (
           Some(back_front),
           Some(reduced_back_i)
@@ -201,12 +203,12 @@ fn build_reduced_back(
   let mut has_back = true;
 
   while has_back {
-    let head = alignment.reduced_back.iter().position(|b| *b == None);
+    let head = alignment.reduced_back.iter().position(|b| b.is_none());
 
     if let Some(mut head_usize) = head {
       let tail = get_tail(head_usize, &alignment.reduced_back);
 
-      let path: Vec<usize> = if head_usize > 0 {
+      let mut path: Vec<usize> = if head_usize > 0 {
         let end_of_path = vec![head_usize - 1];
 
         match &alignment.reduced_back[head_usize - 1] {
@@ -221,6 +223,16 @@ fn build_reduced_back(
         vec![]
       };
 
+      // The path leads up to the previous event, which only sits to the left of
+      // the head while they overlap.
+      while let Some(&last_vertex) = path.last() {
+        if alignment.back[head_usize].contains(&last_vertex) {
+          break;
+        }
+
+        path.pop();
+      }
+
       while head_usize < tail {
         alignment.reduced_back[head_usize] = Some(path.clone());
         let next = alignment.front[head_usize].last();
@@ -243,8 +255,9 @@ fn build_reduced_back(
   build_reduced_front(alignment);
 }
 
-fn generate_alignment(
-  sorted_events: &Vec<sorted_event::SortedEvent>,
+#[doc(hidden)]
+pub fn generate_alignment(
+  sorted_events: &[sorted_event::SortedEvent],
 ) -> Alignment {
   let number_of_events = sorted_events.len();
   let mut alignment = Alignment::new(number_of_events);
@@ -274,12 +287,12 @@ fn add_edge_to_dags(
   from_vertex: usize,
   to_vertex: usize
 ) {
-  if alignment.reduced_front[to_vertex][0] == from_vertex {
+  if alignment.reduced_front[to_vertex].first() == Some(&from_vertex) {
     dag_builder.backward_dag.add_edge(from_vertex, to_vertex);
   }
 
   if let Some(reduce_back_to) = &alignment.reduced_back[to_vertex] {
-    if reduce_back_to[reduce_back_to.len() - 1] == from_vertex {
+    if reduce_back_to.last() == Some(&from_vertex) {
       dag_builder.forward_dag.add_edge(from_vertex, to_vertex);
     }
   }
@@ -291,7 +304,7 @@ fn add_adges_to_dags(
   from_vertex: usize
 ) {
   if let Some(reduced_back_from) = &alignment.reduced_back[from_vertex] {
-    if reduced_back_from.len() > 0 {
+    if !reduced_back_from.is_empty() {
       dag_builder.backward_dag.add_edge(
         from_vertex,
         reduced_back_from[reduced_back_from.len() - 1]
@@ -299,7 +312,7 @@ fn add_adges_to_dags(
     }
   }
 
-  if alignment.reduced_front[from_vertex].len() > 0 {
+  if !alignment.reduced_front[from_vertex].is_empty() {
     dag_builder.forward_dag.add_edge(
       from_vertex,
       alignment.reduced_front[from_vertex][0]
@@ -315,6 +328,21 @@ fn add_adges_to_dags(
   }
 }
 
+#[doc(hidden)]
+pub fn build_dag_builder(
+  alignment: &Alignment
+) -> dag_builder::DagBuilder {
+  let number_of_events = alignment.front.len();
+  let mut dag_builder = dag_builder::DagBuilder::new(number_of_events as u128);
+
+  for i in 0 .. number_of_events {
+    add_adges_to_dags(alignment, &mut dag_builder, i);
+  }
+
+  dag_builder
+}
+
+#[derive(Default)]
 pub struct TimeRespectiveTilingMethod;
 
 impl TimeRespectiveTilingMethod {
@@ -326,21 +354,18 @@ impl TimeRespectiveTilingMethod {
 impl tiling_method::TilingMethod for TimeRespectiveTilingMethod {
   fn tile(
     &self,
-    tiles: &mut Vec<tile::Tile>,
-    sorted_events: &Vec<sorted_event::SortedEvent>,
+    tiles: &mut [tile::Tile],
+    sorted_events: &[sorted_event::SortedEvent],
     _tiler_options: &tiler_options::TilerOptions
-  ) {
-    if sorted_events.len() == 0 {
-      return;
+  ) -> Result<(), tiler_error::TilerError> {
+    if sorted_events.is_empty() {
+      return Ok(());
     }
 
     let alignment = generate_alignment(sorted_events);
-    let mut dag_builder = dag_builder::DagBuilder::new(sorted_events.len() as u128);
 
-    for i in 0 .. tiles.len() {
-      add_adges_to_dags(&alignment, &mut dag_builder, i);
-    }
+    build_dag_builder(&alignment).update_tiles(tiles);
 
-    dag_builder.update_tiles(tiles);
+    Ok(())
   }
 }
@@ -0,0 +1,283 @@
+use wasm_bindgen::prelude::*;
+use crate::event;
+use crate::layout_quality;
+use crate::tiler;
+use crate::tiler_options;
+use crate::tiler_error;
+use crate::tiling_method;
+use crate::utils;
+
+const TILE_STRIDE: usize = 4;
+
+#[wasm_bindgen]
+pub struct WasmEvent {
+  id: String,
+  pub start: f64,
+  pub end: f64,
+  pub all_day: bool,
+}
+
+#[wasm_bindgen]
+impl WasmEvent {
+  #[wasm_bindgen(constructor)]
+  pub fn new(
+    id: String,
+    start: f64,
+    end: f64
+  ) -> WasmEvent {
+    WasmEvent {
+      id,
+      start,
+      end,
+      all_day: false
+    }
+  }
+
+  #[wasm_bindgen(getter)]
+  pub fn id(&self) -> String {
+    self.id.clone()
+  }
+}
+
+#[wasm_bindgen]
+pub struct WasmTile {
+  id: String,
+  pub x: f64,
+  pub y: f64,
+  pub dx: f64,
+  pub dy: f64,
+  pub z: u32,
+  pub all_day: bool,
+  /// Lane of an all-day event counted from the top, and zero otherwise.
+  pub lane: u32,
+}
+
+#[wasm_bindgen]
+impl WasmTile {
+  #[wasm_bindgen(getter)]
+  pub fn id(&self) -> String {
+    self.id.clone()
+  }
+}
+
+fn build_tiler(
+  events: Vec<event::Event>,
+  tiling_method_name: tiling_method::TilingMethodName,
+  tiler_options: &tiler_options::TilerOptions,
+  objective: Option<layout_quality::LayoutObjective>
+) -> Result<tiler::Tiler<Box<dyn tiling_method::TilingMethod>>, tiler_error::TilerError> {
+  utils::set_panic_hook();
+
+  let mut tiler = tiler::Tiler::new(
+    events,
+    *tiler_options,
+    tiling_method::get_tiling_method_with_objective(tiling_method_name, objective.unwrap_or_default())
+  )?;
+
+  tiler.tile()?;
+
+  Ok(tiler)
+}
+
+/// Tiles `wasm_events`, returning their tiles in the same order. `objective`
+/// picks what `TilingMethodName.Auto` ranks layouts by, and may be left out.
+#[wasm_bindgen(js_name = tileEvents)]
+pub fn tile_events(
+  wasm_events: Vec<WasmEvent>,
+  tiling_method_name: tiling_method::TilingMethodName,
+  tiler_options: &tiler_options::TilerOptions,
+  objective: Option<layout_quality::LayoutObjective>
+) -> Result<Vec<WasmTile>, JsError> {
+  let events: Vec<event::Event> = wasm_events.iter().map(|e| event::Event {
+    start: e.start,
+    end: e.end,
+    all_day: e.all_day
+  }).collect();
+
+  let tiler = build_tiler(events, tiling_method_name, tiler_options, objective)?;
+  let mut lanes: Vec<u32> = vec![0; wasm_events.len()];
+
+  for all_day_tile in tiler.all_day_tiles.iter() {
+    lanes[all_day_tile.original_index] = all_day_tile.lane as u32;
+  }
+
+  Ok(tiler.get_tiles_in_original_order()
+       .iter()
+       .zip(wasm_events)
+       .zip(lanes)
+       .map(|((tile, wasm_event), lane)| WasmTile {
+         id: wasm_event.id,
+         x: tile.x,
+         y: tile.y,
+         dx: tile.dx,
+         dy: tile.dy,
+         z: tile.z,
+         all_day: wasm_event.all_day,
+         lane
+       })
+       .collect())
+}
+
+fn tile_packed_events(
+  starts: &[f64],
+  ends: &[f64],
+  tiling_method_name: tiling_method::TilingMethodName,
+  tiler_options: &tiler_options::TilerOptions,
+  tiles: &mut [f64],
+  mut z: Option<&mut [u32]>,
+  objective: Option<layout_quality::LayoutObjective>
+) -> Result<(), JsError> {
+  if starts.len() != ends.len() {
+    return Err(JsError::new("starts and ends must have the same length"));
+  }
+
+  if tiles.len() < starts.len() * TILE_STRIDE {
+    return Err(JsError::new("tiles must hold four values per event"));
+  }
+
+  if z.as_ref().is_some_and(|z| z.len() < starts.len()) {
+    return Err(JsError::new("z must hold one value per event"));
+  }
+
+  let events: Vec<event::Event> = starts.iter().zip(ends.iter()).map(|(&start, &end)| {
+    event::Event::new(start, end)
+  }).collect();
+
+  let tiler = build_tiler(events, tiling_method_name, tiler_options, objective)?;
+
+  for sorted_event in tiler.sorted_events.iter() {
+    let tile = tiler.tiles[sorted_event.sorted_index];
+    let offset = sorted_event.original_index * TILE_STRIDE;
+
+    tiles[offset] = tile.x;
+    tiles[offset + 1] = tile.dx;
+    tiles[offset + 2] = tile.y;
+    tiles[offset + 3] = tile.dy;
+
+    if let Some(z) = z.as_deref_mut() {
+      z[sorted_event.original_index] = tile.z;
+    }
+  }
+
+  Ok(())
+}
+
+/// Tiles events given as packed `starts` / `ends` buffers, writing
+/// `x, dx, y, dy` for each event into `tiles` at the event's original index.
+/// `objective` is as for `tileEvents`. The buffers are copied into and out of
+/// wasm memory, which `EventBuffers` avoids.
+#[wasm_bindgen(js_name = tileEventBuffers)]
+pub fn tile_event_buffers(
+  starts: &[f64],
+  ends: &[f64],
+  tiling_method_name: tiling_method::TilingMethodName,
+  tiler_options: &tiler_options::TilerOptions,
+  tiles: &mut [f64],
+  objective: Option<layout_quality::LayoutObjective>
+) -> Result<(), JsError> {
+  tile_packed_events(starts, ends, tiling_method_name, tiler_options, tiles, None, objective)
+}
+
+/// Like `tileEventBuffers`, also writing each event's stacking order into `z`
+/// at its original index, for methods such as cascade that overlap tiles.
+#[wasm_bindgen(js_name = tileEventBuffersWithZ)]
+pub fn tile_event_buffers_with_z(
+  starts: &[f64],
+  ends: &[f64],
+  tiling_method_name: tiling_method::TilingMethodName,
+  tiler_options: &tiler_options::TilerOptions,
+  tiles: &mut [f64],
+  z: &mut [u32],
+  objective: Option<layout_quality::LayoutObjective>
+) -> Result<(), JsError> {
+  tile_packed_events(starts, ends, tiling_method_name, tiler_options, tiles, Some(z), objective)
+}
+
+/// Packed event and tile buffers that live in wasm memory. JS fills `starts`
+/// and `ends` and reads `tiles` and `z` through typed array views at the
+/// pointers below, so tiling copies nothing across the boundary. `tiles` and
+/// `z` are laid out as for `tileEventBuffersWithZ`. Tiling may grow wasm
+/// memory, which detaches every view, so views are made again after `tile`.
+#[wasm_bindgen]
+pub struct EventBuffers {
+  starts: Vec<f64>,
+  ends: Vec<f64>,
+  tiles: Vec<f64>,
+  z: Vec<u32>,
+}
+
+#[wasm_bindgen]
+impl EventBuffers {
+  #[wasm_bindgen(constructor)]
+  pub fn new(number_of_events: usize) -> EventBuffers {
+    EventBuffers {
+      starts: vec![0.0; number_of_events],
+      ends: vec![0.0; number_of_events],
+      tiles: vec![0.0; number_of_events * TILE_STRIDE],
+      z: vec![0; number_of_events]
+    }
+  }
+
+  #[wasm_bindgen(getter, js_name = numberOfEvents)]
+  pub fn number_of_events(&self) -> usize {
+    self.starts.len()
+  }
+
+  #[wasm_bindgen(js_name = startsPtr)]
+  pub fn starts_ptr(&mut self) -> *mut f64 {
+    self.starts.as_mut_ptr()
+  }
+
+  #[wasm_bindgen(js_name = endsPtr)]
+  pub fn ends_ptr(&mut self) -> *mut f64 {
+    self.ends.as_mut_ptr()
+  }
+
+  #[wasm_bindgen(js_name = tilesPtr)]
+  pub fn tiles_ptr(&self) -> *const f64 {
+    self.tiles.as_ptr()
+  }
+
+  #[wasm_bindgen(js_name = zPtr)]
+  pub fn z_ptr(&self) -> *const u32 {
+    self.z.as_ptr()
+  }
+
+  /// Tiles the events in `starts` and `ends` into `tiles` and `z`.
+  /// `objective` is as for `tileEvents`.
+  pub fn tile(
+    &mut self,
+    tiling_method_name: tiling_method::TilingMethodName,
+    tiler_options: &tiler_options::TilerOptions,
+    objective: Option<layout_quality::LayoutObjective>
+  ) -> Result<(), JsError> {
+    tile_packed_events(
+      &self.starts,
+      &self.ends,
+      tiling_method_name,
+      tiler_options,
+      &mut self.tiles,
+      Some(&mut self.z),
+      objective
+    )
+  }
+}
+
+/// The buffers as Rust sees them, which JS reaches through the pointers.
+impl EventBuffers {
+  pub fn starts_mut(&mut self) -> &mut [f64] {
+    &mut self.starts
+  }
+
+  pub fn ends_mut(&mut self) -> &mut [f64] {
+    &mut self.ends
+  }
+
+  pub fn tiles(&self) -> &[f64] {
+    &self.tiles
+  }
+
+  pub fn z(&self) -> &[u32] {
+    &self.z
+  }
+}
@@ -0,0 +1,180 @@
+use crate::event;
+use crate::tile;
+use crate::tiler;
+use crate::tiling_method;
+use crate::tiler_options;
+use crate::tiler_error;
+use std::ops::Range;
+
+pub const SECONDS_PER_HOUR: f64 = 3600.0;
+pub const HOURS_PER_DAY: f64 = 24.0;
+pub const SECONDS_PER_DAY: f64 = SECONDS_PER_HOUR * HOURS_PER_DAY;
+
+/// The part of an event that falls within one day, timed in hours since that
+/// day's midnight.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct DaySegment {
+  pub original_index: usize,
+  pub day_index: usize,
+  pub event: event::Event,
+  /// Whether the event started on an earlier day.
+  pub continues_before: bool,
+  /// Whether the event ends on a later day.
+  pub continues_after: bool,
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct WeekTile {
+  pub original_index: usize,
+  pub day_index: usize,
+  pub tile: tile::Tile,
+  pub continues_before: bool,
+  pub continues_after: bool,
+}
+
+/// Days, counted from `week_start` and limited to the first `number_of_days`,
+/// that `event` covers. An event ending exactly at midnight does not reach
+/// into the next day, and a zero-duration event covers the day it falls on.
+pub fn get_covered_days(
+  event: &event::Event,
+  week_start: f64,
+  number_of_days: usize
+) -> Range<usize> {
+  let first_day = ((event.start - week_start) / SECONDS_PER_DAY).floor();
+  let last_day = if event.end > event.start {
+    ((event.end - week_start) / SECONDS_PER_DAY).ceil() - 1.0
+  } else {
+    first_day
+  };
+
+  // Written to also cover no days when `week_start` is not finite.
+  if !(last_day >= 0.0 && first_day < number_of_days as f64) {
+    return 0 .. 0;
+  }
+
+  (first_day.max(0.0) as usize) .. ((last_day + 1.0).min(number_of_days as f64) as usize)
+}
+
+/// Whether `event` covers any of `range_start .. range_end`, by the same rules
+/// as `get_covered_days`.
+pub fn covers_range(
+  event: &event::Event,
+  range_start: f64,
+  range_end: f64
+) -> bool {
+  event.start < range_end && (event.end > range_start || event.start >= range_start)
+}
+
+/// Cuts the part of `event` that falls between `day_start` and `day_end` out of
+/// it, timed in hours since `day_start`.
+pub fn cut_day_segment(
+  original_index: usize,
+  event: &event::Event,
+  day_index: usize,
+  day_start: f64,
+  day_end: f64
+) -> DaySegment {
+  DaySegment {
+    original_index,
+    day_index,
+    event: event::Event {
+      start: (event.start.max(day_start) - day_start) / SECONDS_PER_HOUR,
+      end: (event.end.min(day_end) - day_start) / SECONDS_PER_HOUR,
+      all_day: event.all_day
+    },
+    continues_before: event.start < day_start,
+    continues_after: event.end > day_end
+  }
+}
+
+/// Cuts every event at midnight into one segment per day it covers, and
+/// returns the segments of each day in order of original index. Parts of
+/// events outside the days are dropped.
+pub fn split_into_days(
+  events: &[event::Event],
+  week_start: f64,
+  number_of_days: usize
+) -> Vec<Vec<DaySegment>> {
+  let mut days: Vec<Vec<DaySegment>> = vec![vec![]; number_of_days];
+
+  for (original_index, event) in events.iter().enumerate() {
+    let covered_days = get_covered_days(event, week_start, number_of_days);
+
+    for (day_index, segments) in covered_days.clone().zip(days[covered_days].iter_mut()) {
+      let day_start = week_start + (day_index as f64) * SECONDS_PER_DAY;
+      let day_end = day_start + SECONDS_PER_DAY;
+
+      segments.push(cut_day_segment(original_index, event, day_index, day_start, day_end));
+    }
+  }
+
+  days
+}
+
+/// Lays out events with absolute times, in seconds from the same origin as
+/// `week_start`, over consecutive days that each start at a midnight.
+pub struct WeekTiler<T: tiling_method::TilingMethod> {
+  pub events: Vec<event::Event>,
+  pub week_start: f64,
+  pub days: Vec<Vec<DaySegment>>,
+  pub tiles: Vec<WeekTile>,
+  pub tiling_method: T,
+  pub tiler_options: tiler_options::TilerOptions,
+}
+
+impl<T: tiling_method::TilingMethod> WeekTiler<T> {
+  pub fn new(
+    events: Vec<event::Event>,
+    week_start: f64,
+    number_of_days: usize,
+    tiler_options: tiler_options::TilerOptions,
+    tiling_method: T
+  ) -> Result<WeekTiler<T>, tiler_error::TilerError> {
+    tiler::validate_events(&events)?;
+
+    let days = split_into_days(&events, week_start, number_of_days);
+
+    Ok(WeekTiler::<T> {
+      events,
+      week_start,
+      days,
+      tiles: vec![],
+      tiling_method,
+      tiler_options,
+    })
+  }
+
+  /// Tiles the timed segments of every day on its own, leaving `tiles` ordered
+  /// by day and then by original index. All-day events get no tiles, and can
+  /// be stacked above the days with `month_layout` over a single week.
+  pub fn tile(&mut self) -> Result<(), tiler_error::TilerError> {
+    self.tiles = vec![];
+
+    for day_segments in self.days.iter() {
+      let segments: Vec<&DaySegment> = day_segments.iter().filter(|segment| !segment.event.all_day).collect();
+      let day_events: Vec<event::Event> = segments.iter().map(|segment| segment.event).collect();
+      let sorted_events = tiler::sort_events(&day_events, &self.tiler_options);
+      let mut tiles: Vec<tile::Tile> = sorted_events.iter().map(|sorted_event| sorted_event.tile).collect();
+
+      self.tiling_method.tile(&mut tiles, &sorted_events, &self.tiler_options)?;
+
+      let mut day_tiles = tiles.clone();
+
+      for sorted_event in sorted_events.iter() {
+        day_tiles[sorted_event.original_index] = tiles[sorted_event.sorted_index];
+      }
+
+      for (segment, tile) in segments.iter().zip(day_tiles) {
+        self.tiles.push(WeekTile {
+          original_index: segment.original_index,
+          day_index: segment.day_index,
+          tile,
+          continues_before: segment.continues_before,
+          continues_after: segment.continues_after
+        });
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "serde")]
+
+use std::process::{Command, Output};
+
+fn calendar_tiler(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_calendar-tiler"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn lists_every_tiling_method() {
+    let output = calendar_tiler(&["--list-methods"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+
+    for name in ["basic", "space-filling", "time-respective", "interval-coloring", "expand-right", "cascade", "optimized", "auto"].iter() {
+        assert!(stdout.lines().any(|line| line.starts_with(name)));
+    }
+}
+
+#[test]
+fn help_says_which_input_needs_the_ics_feature() {
+    let output = calendar_tiler(&["--help"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.contains("--features ics"));
+}
+
+#[test]
+fn tiles_with_the_basic_method_by_default() {
+    let default_output = calendar_tiler(&["--seed", "7", "--count", "12"]);
+    let basic_output = calendar_tiler(&["--seed", "7", "--count", "12", "--method", "basic"]);
+
+    assert!(default_output.status.success());
+    assert_eq!(default_output.stdout, basic_output.stdout);
+}
+
+#[test]
+fn ranks_auto_layouts_by_the_objective() {
+    let output = calendar_tiler(&["--seed", "7", "--method", "auto", "--objective", "area-utilization"]);
+
+    assert!(output.status.success());
+    assert_eq!(calendar_tiler(&["--seed", "7", "--objective", "area-utilization"]).status.code(), Some(2));
+    assert_eq!(calendar_tiler(&["--seed", "7", "--method", "auto", "--objective", "widest"]).status.code(), Some(2));
+}
+
+#[test]
+fn rejects_unknown_tiling_methods() {
+    let output = calendar_tiler(&["--method", "3"]);
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn seeded_random_days_are_reproducible() {
+    let args = ["--seed", "7", "--count", "12", "--method", "basic"];
+    let first = calendar_tiler(&args);
+    let second = calendar_tiler(&args);
+    let tiles: Vec<serde_json::Value> = serde_json::from_slice(&first.stdout).unwrap();
+
+    assert!(first.status.success());
+    assert_eq!(tiles.len(), 12);
+    assert_eq!(first.stdout, second.stdout);
+}
+
+#[test]
+fn renders_svg_with_a_labelled_rect_per_tile() {
+    let output = calendar_tiler(&["--seed", "7", "--count", "5", "--format", "svg"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.starts_with("<svg"));
+    assert_eq!(stdout.matches("<rect x=").count(), 5);
+    assert_eq!(stdout.matches(">4</text>").count(), 1);
+}
+
+#[cfg(feature = "ics")]
+#[test]
+fn tiles_a_day_of_an_icalendar_file() {
+    let fixture = format!("{}/tests/fixtures/ics/fall-back.ics", env!("CARGO_MANIFEST_DIR"));
+    let output = calendar_tiler(&[
+        "--input", &fixture, "--date", "2024-11-03", "--time-zone", "America/New_York", "--method", "basic",
+    ]);
+    let tiles: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(tiles.len(), 6);
+    assert_eq!(tiles[0]["id"], "early-sync@example.com");
+    assert_eq!(tiles[0]["payload"]["summary"], "Early sync");
+    assert_eq!(tiles[2]["all_day_lane"], 0);
+
+    let svg = calendar_tiler(&["--input", &fixture, "--date", "2024-11-03", "--format", "svg"]);
+
+    assert!(String::from_utf8(svg.stdout).unwrap().contains(">Late sync</text>"));
+}
+
+#[cfg(feature = "ics")]
+#[test]
+fn renders_an_icalendar_day_on_its_wall_clock() {
+    let fixture = format!("{}/tests/fixtures/ics/fall-back.ics", env!("CARGO_MANIFEST_DIR"));
+    let output = calendar_tiler(&[
+        "--input", &fixture, "--date", "2024-11-03", "--time-zone", "America/New_York", "--format", "svg",
+    ]);
+    let svg = String::from_utf8(output.stdout).unwrap();
+
+    // The day falls back, so it is 25 hours tall and reads 1:00 twice.
+    assert!(output.status.success());
+    assert!(svg.contains("height=\"1000\""));
+    assert_eq!(svg.matches(">1:00</text>").count(), 2);
+    assert_eq!(svg.matches(">2:00</text>").count(), 1);
+}
+
+#[cfg(feature = "ics")]
+#[test]
+fn icalendar_input_needs_a_date() {
+    let fixture = format!("{}/tests/fixtures/ics/fall-back.ics", env!("CARGO_MANIFEST_DIR"));
+    let output = calendar_tiler(&["--input", &fixture]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr).unwrap().contains("--date"));
+}
@@ -0,0 +1,214 @@
+#![cfg(feature = "ics")]
+
+use calendar_tiler::ics::{self, IcsError, IcsTime};
+use calendar_tiler::json;
+use calendar_tiler::{BasicTilingMethod, DisplayDay, Event, TilerOptions};
+use serde_json::json;
+use std::fs;
+
+fn read_fixture(name: &str) -> String {
+    fs::read_to_string(format!("{}/tests/fixtures/ics/{}", env!("CARGO_MANIFEST_DIR"), name)).unwrap()
+}
+
+fn wrap_event(lines: &str) -> String {
+    format!("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n{}\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n", lines)
+}
+
+#[test]
+fn reads_every_event_with_its_properties() {
+    let ics_events = ics::parse_ics(&read_fixture("fall-back.ics")).unwrap();
+    let uids: Vec<&str> = ics_events.iter().map(|ics_event| ics_event.uid.as_deref().unwrap()).collect();
+
+    assert_eq!(uids, vec![
+        "early-sync@example.com",
+        "late-sync@example.com",
+        "offsite@example.com",
+        "review@example.com",
+        "red-eye@example.com",
+        "lunch@example.com",
+        "next-week@example.com",
+    ]);
+    assert_eq!(ics_events[2].summary.as_deref(), Some("Team offsite, day two"));
+    assert_eq!(
+        ics_events[3].summary.as_deref(),
+        Some("Quarterly review with a summary long enough that the exporting tool folded it across two lines")
+    );
+    assert!(matches!(&ics_events[3].start, IcsTime::Zoned { time_zone_name, .. } if time_zone_name == "America/New_York"));
+    assert!(matches!(ics_events[5].start, IcsTime::Floating(_)));
+    assert_eq!(ics_events[3].line_number, 30);
+}
+
+#[test]
+fn places_events_on_a_day_with_a_repeated_hour() {
+    let ics_events = ics::parse_ics(&read_fixture("fall-back.ics")).unwrap();
+    let day = DisplayDay::new("2024-11-03", "America/New_York").unwrap();
+    let (json_events, skipped) = ics::get_json_events(&ics_events, &day);
+
+    assert!(skipped.is_empty());
+    let placed: Vec<(String, f64, f64, bool)> = json_events.iter()
+        .map(|json_event| {
+            let event = json_event.event;
+
+            (json_event.id.as_ref().unwrap().as_str().unwrap().to_string(), event.start, event.end, event.all_day)
+        })
+        .collect();
+
+    assert_eq!(placed, vec![
+        ("early-sync@example.com".to_string(), 1.5, 3.0, false),
+        ("late-sync@example.com".to_string(), 2.5, 3.0, false),
+        ("offsite@example.com".to_string(), 0.0, 25.0, true),
+        ("review@example.com".to_string(), 10.0, 11.5, false),
+        ("red-eye@example.com".to_string(), 24.0, 25.0, false),
+        ("lunch@example.com".to_string(), 13.0, 14.0, false),
+    ]);
+    assert_eq!(json_events[0].payload, Some(json!({ "summary": "Early sync" })));
+
+    let json_tiles = json::tile_json_events(json_events, TilerOptions::new(0.0001), BasicTilingMethod::new()).unwrap();
+
+    assert_eq!(json_tiles[2].all_day_lane, Some(0));
+    assert!(json_tiles[0].tile.x < json_tiles[1].tile.x);
+}
+
+#[test]
+fn floating_times_and_dates_follow_the_display_zone() {
+    let ics_events = ics::parse_ics(&read_fixture("fall-back.ics")).unwrap();
+    let day = DisplayDay::new("2024-11-03", "Europe/Berlin").unwrap();
+    let events = ics::get_events_in_range(&ics_events, day.start, day.end, day.get_time_zone()).events;
+    let lunch = events.iter().find(|(i, _)| *i == 5).unwrap().1;
+    let offsite = events.iter().find(|(i, _)| *i == 2).unwrap().1;
+
+    assert_eq!(day.get_position(lunch.start), 12.0);
+    assert_eq!((offsite.start, offsite.end), (day.start, day.end));
+}
+
+#[test]
+fn fills_in_missing_ends() {
+    let all_day = &ics::parse_ics(&wrap_event("DTSTART;VALUE=DATE:20240310")).unwrap()[0];
+    let instant = &ics::parse_ics(&wrap_event("DTSTART:20240310T090000Z")).unwrap()[0];
+    let weekly = &ics::parse_ics(&wrap_event("DTSTART;VALUE=DATE:20240310\r\nDURATION:P1W")).unwrap()[0];
+    let new_york = DisplayDay::new("2024-03-10", "America/New_York").unwrap();
+    let time_zone = new_york.get_time_zone();
+
+    let all_day_event = all_day.to_event(time_zone).unwrap();
+    let instant_event = instant.to_event(time_zone).unwrap();
+    let weekly_event = weekly.to_event(time_zone).unwrap();
+
+    assert_eq!((all_day_event.start, all_day_event.end), (new_york.start, new_york.end));
+    assert_eq!(instant_event.start, instant_event.end);
+    assert_eq!(weekly_event.end - weekly_event.start, 7.0 * 86_400.0 - 3600.0);
+}
+
+#[test]
+fn reports_where_the_file_is_broken() {
+    assert_eq!(
+        ics::parse_ics(&wrap_event("SUMMARY:No start")).err(),
+        Some(IcsError::MissingStart { line_number: 2 })
+    );
+    assert_eq!(
+        ics::parse_ics(&wrap_event("DTSTART:2024-03-10")).err(),
+        Some(IcsError::InvalidValue {
+            line_number: 3,
+            name: "DTSTART".to_string(),
+            value: "2024-03-10".to_string()
+        })
+    );
+    assert_eq!(
+        ics::parse_ics(&wrap_event("DTSTART:20240310T090000Z\r\nBEGIN:VALARM")).err(),
+        Some(IcsError::InvalidLine { line_number: 5 })
+    );
+    assert_eq!(
+        ics::parse_ics("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nDTSTART:20240310T090000Z\r\n").err(),
+        Some(IcsError::UnclosedComponent { line_number: 2, name: "VEVENT".to_string() })
+    );
+
+    let unknown_zone = ics::parse_ics(&wrap_event("DTSTART;TZID=Eastern Standard Time:20240310T090000")).unwrap();
+
+    assert_eq!(
+        unknown_zone[0].to_event(&jiff::tz::TimeZone::UTC).err(),
+        Some(IcsError::UnknownTimeZone { line_number: 2, name: "Eastern Standard Time".to_string() })
+    );
+}
+
+#[test]
+fn skips_events_that_fail_to_resolve() {
+    let text = read_fixture("fall-back.ics").replace("TZID=America/Los_Angeles", "TZID=Mars/Olympus_Mons");
+    let ics_events = ics::parse_ics(&text).unwrap();
+    let day = DisplayDay::new("2024-11-03", "America/New_York").unwrap();
+    let (json_events, skipped) = ics::get_json_events(&ics_events, &day);
+    let ids: Vec<&str> = json_events.iter().map(|json_event| json_event.id.as_ref().unwrap().as_str().unwrap()).collect();
+
+    assert_eq!(ids, vec![
+        "early-sync@example.com",
+        "late-sync@example.com",
+        "offsite@example.com",
+        "review@example.com",
+        "lunch@example.com",
+    ]);
+    assert_eq!(skipped, vec![
+        (4, IcsError::UnknownTimeZone { line_number: 42, name: "Mars/Olympus_Mons".to_string() }),
+    ]);
+}
+
+#[test]
+fn resolves_unknown_zone_names_from_the_vtimezone() {
+    let ics_events = ics::parse_ics(&read_fixture("windows-zones.ics")).unwrap();
+    let day = DisplayDay::new("2024-11-03", "America/New_York").unwrap();
+    let (json_events, skipped) = ics::get_json_events(&ics_events, &day);
+    let placed: Vec<(f64, f64)> = json_events.iter().map(|json_event| (json_event.event.start, json_event.event.end)).collect();
+
+    assert!(skipped.is_empty());
+    assert_eq!(placed, vec![(10.0, 10.25), (10.5, 11.5)]);
+
+    let summer_standup = ics_events[2].to_event(&jiff::tz::TimeZone::UTC).unwrap();
+    let expected_start: jiff::Timestamp = "2024-07-10T13:00:00Z".parse().unwrap();
+
+    assert_eq!(summer_standup.start, expected_start.as_second() as f64);
+}
+
+fn get_local_starts(events: &[(usize, Event)]) -> Vec<(usize, String)> {
+    events.iter().map(|(i, event)| {
+        let start = jiff::Timestamp::from_second(event.start as i64).unwrap().in_tz("America/New_York").unwrap();
+
+        (*i, start.datetime().strftime("%F %R").to_string())
+    }).collect()
+}
+
+#[test]
+fn expands_recurrences_within_the_range() {
+    let ics_events = ics::parse_ics(&read_fixture("recurring.ics")).unwrap();
+    let monday = DisplayDay::new("2024-11-04", "America/New_York").unwrap();
+    let sunday = DisplayDay::new("2024-11-10", "America/New_York").unwrap();
+    let events_in_range = ics::get_events_in_range(&ics_events, monday.start, sunday.end, monday.get_time_zone());
+    let local_starts = get_local_starts(&events_in_range.events);
+
+    assert_eq!(local_starts, vec![
+        (0, "2024-11-04 09:00".to_string()),
+        (0, "2024-11-09 10:00".to_string()),
+        (1, "2024-11-08 11:00".to_string()),
+        (4, "2024-11-04 09:00".to_string()),
+        (4, "2024-11-05 09:00".to_string()),
+        (4, "2024-11-06 09:00".to_string()),
+    ]);
+    assert!(events_in_range.events.iter().all(|(i, event)| *i != 0 || event.end - event.start == 900.0));
+    assert_eq!(events_in_range.skipped, vec![
+        (5, IcsError::UnsupportedRule { line_number: 40, rule: "FREQ=HOURLY".to_string() }),
+    ]);
+}
+
+#[test]
+fn stops_recurrences_at_their_count_and_skips_missing_dates() {
+    let ics_events = ics::parse_ics(&read_fixture("recurring.ics")).unwrap();
+    let new_year = DisplayDay::new("2024-01-01", "America/New_York").unwrap();
+    let new_years_eve = DisplayDay::new("2024-12-31", "America/New_York").unwrap();
+    let events_in_range = ics::get_events_in_range(&ics_events, new_year.start, new_years_eve.end, new_year.get_time_zone());
+    let local_starts = get_local_starts(&events_in_range.events);
+
+    assert_eq!(local_starts.iter().filter(|(i, _)| *i == 2).collect::<Vec<_>>(), vec![
+        &(2, "2024-09-27 16:00".to_string()),
+        &(2, "2024-10-25 16:00".to_string()),
+        &(2, "2024-11-29 16:00".to_string()),
+    ]);
+    assert_eq!(local_starts.iter().filter(|(i, _)| *i == 3).collect::<Vec<_>>(), vec![
+        &(3, "2024-02-29 00:00".to_string()),
+    ]);
+}
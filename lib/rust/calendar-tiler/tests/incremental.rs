@@ -0,0 +1,203 @@
+use calendar_tiler::{
+    get_tiling_method, AllDayTile, Event, EventId, Tile, Tiler, TilerError, TilerOptions, TilingMethod,
+    TilingMethodName, SpaceFillingTilingMethod,
+};
+use proptest::prelude::*;
+
+const ERROR_BOUND: f64 = 0.0001;
+
+#[derive(Clone, Debug)]
+enum Operation {
+    Insert(Event),
+    Update(usize, Event),
+    Remove(usize),
+}
+
+fn event() -> impl Strategy<Value = Event> {
+    (0 .. 96_u32, 0 .. 12_u32, prop::bool::weighted(0.15)).prop_map(|(start, duration, all_day)| {
+        let (start, end) = (start as f64 / 4.0, (start + duration).min(96) as f64 / 4.0);
+
+        if all_day { Event::all_day(start, end) } else { Event::new(start, end) }
+    })
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        event().prop_map(Operation::Insert),
+        (any::<usize>(), event()).prop_map(|(i, event)| Operation::Update(i, event)),
+        any::<usize>().prop_map(Operation::Remove),
+    ]
+}
+
+fn tile_from_scratch<T: TilingMethod>(events: Vec<Event>, tiling_method: T) -> (Vec<Tile>, Vec<AllDayTile>) {
+    let mut tiler = Tiler::new(events, TilerOptions::new(ERROR_BOUND), tiling_method).unwrap();
+
+    tiler.tile().unwrap();
+
+    (tiler.get_tiles_in_original_order(), tiler.all_day_tiles)
+}
+
+fn assert_matches_retiling(
+    tiling_method_name: TilingMethodName,
+    events: Vec<Event>,
+    operations: Vec<Operation>
+) -> Result<(), TestCaseError> {
+    let mut tiler = Tiler::new(
+        events,
+        TilerOptions::new(ERROR_BOUND),
+        get_tiling_method(tiling_method_name)
+    ).unwrap();
+
+    tiler.tile().unwrap();
+
+    for operation in operations {
+        let previous_tiles: Vec<(EventId, Tile)> = tiler.event_ids.iter().copied()
+            .zip(tiler.get_tiles_in_original_order())
+            .collect();
+
+        let changed_tiles = match operation {
+            Operation::Insert(event) => tiler.insert_event(event).map(|(_, changed_tiles)| changed_tiles),
+            Operation::Update(_, _) | Operation::Remove(_) if tiler.events.is_empty() => continue,
+            Operation::Update(i, event) => tiler.update_event(tiler.event_ids[i % tiler.events.len()], event),
+            Operation::Remove(i) => tiler.remove_event(tiler.event_ids[i % tiler.events.len()]),
+        }.unwrap();
+
+        let tiles = tiler.get_tiles_in_original_order();
+        let (expected_tiles, expected_all_day_tiles) = tile_from_scratch(
+            tiler.events.clone(),
+            get_tiling_method(tiling_method_name)
+        );
+
+        prop_assert_eq!(&tiles, &expected_tiles);
+        prop_assert_eq!(&tiler.all_day_tiles, &expected_all_day_tiles);
+
+        // All-day events leave the day grid, so their tiles are not reported.
+        for (original_index, tile) in tiles.iter().enumerate() {
+            let event_id = tiler.event_ids[original_index];
+
+            if !tiler.events[original_index].all_day && !previous_tiles.contains(&(event_id, *tile)) {
+                prop_assert!(changed_tiles.contains(&(event_id, *tile)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn incremental_edits_match_tiling_from_scratch(
+        events in prop::collection::vec(event(), 0 ..= 20),
+        operations in prop::collection::vec(operation(), 1 ..= 10)
+    ) {
+        for tiling_method_name in TilingMethodName::ALL.iter() {
+            assert_matches_retiling(*tiling_method_name, events.clone(), operations.clone())?;
+        }
+    }
+}
+
+#[test]
+fn update_only_reports_tiles_in_the_affected_cluster() {
+    let mut tiler = Tiler::new(
+        vec![
+            Event::new(9.0, 10.0),
+            Event::new(9.5, 10.5),
+            Event::new(13.0, 14.0),
+        ],
+        TilerOptions::new(ERROR_BOUND),
+        SpaceFillingTilingMethod::new()
+    ).unwrap();
+
+    tiler.tile().unwrap();
+
+    let changed_tiles = tiler.update_event(EventId(1), Event::new(13.5, 14.5)).unwrap();
+
+    assert_eq!(changed_tiles, vec![
+        (EventId(0), Tile { x: 0.0, y: 9.0, dx: 1.0, dy: 1.0, z: 0 }),
+        (EventId(1), Tile { x: 0.5, y: 13.5, dx: 0.5, dy: 1.0, z: 0 }),
+        (EventId(2), Tile { x: 0.0, y: 13.0, dx: 0.5, dy: 1.0, z: 0 }),
+    ]);
+
+    let (event_id, changed_tiles) = tiler.insert_event(Event::new(20.0, 21.0)).unwrap();
+
+    assert_eq!(event_id, EventId(3));
+    assert_eq!(changed_tiles, vec![(EventId(3), Tile { x: 0.0, y: 20.0, dx: 1.0, dy: 1.0, z: 0 })]);
+}
+
+#[test]
+fn event_ids_outlast_removing_earlier_events() {
+    let mut tiler = Tiler::new(
+        vec![
+            Event::new(9.0, 10.0),
+            Event::new(11.0, 12.0),
+            Event::new(11.5, 12.5),
+        ],
+        TilerOptions::new(ERROR_BOUND),
+        SpaceFillingTilingMethod::new()
+    ).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert_eq!(tiler.remove_event(EventId(0)), Ok(vec![]));
+    assert_eq!(tiler.event_ids, vec![EventId(1), EventId(2)]);
+    assert_eq!(tiler.get_original_index(EventId(2)), Some(1));
+
+    let changed_tiles = tiler.update_event(EventId(2), Event::new(14.0, 15.0)).unwrap();
+
+    assert_eq!(changed_tiles, vec![
+        (EventId(1), Tile { x: 0.0, y: 11.0, dx: 1.0, dy: 1.0, z: 0 }),
+        (EventId(2), Tile { x: 0.0, y: 14.0, dx: 1.0, dy: 1.0, z: 0 }),
+    ]);
+    assert_eq!(tiler.events, vec![Event::new(11.0, 12.0), Event::new(14.0, 15.0)]);
+
+    let (event_id, _) = tiler.insert_event(Event::new(16.0, 17.0)).unwrap();
+
+    assert_eq!(event_id, EventId(3));
+}
+
+#[test]
+fn rejects_unknown_and_invalid_events() {
+    let mut tiler = Tiler::new(
+        vec![Event::new(9.0, 10.0)],
+        TilerOptions::new(ERROR_BOUND),
+        SpaceFillingTilingMethod::new()
+    ).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert_eq!(tiler.remove_event(EventId(1)), Err(TilerError::UnknownEvent { event_id: EventId(1) }));
+    assert!(tiler.update_event(EventId(0), Event::new(10.0, 9.0)).is_err());
+    assert_eq!(tiler.events, vec![Event::new(9.0, 10.0)]);
+    assert_eq!(tiler.remove_event(EventId(0)), Ok(vec![]));
+    assert!(tiler.tiles.is_empty());
+    assert_eq!(tiler.update_event(EventId(0), Event::new(9.0, 10.0)), Err(TilerError::UnknownEvent { event_id: EventId(0) }));
+}
+
+#[test]
+fn all_day_edits_only_move_lanes() {
+    let mut tiler = Tiler::new(
+        vec![
+            Event::new(9.0, 10.0),
+            Event::all_day(0.0, 24.0),
+        ],
+        TilerOptions::new(ERROR_BOUND),
+        SpaceFillingTilingMethod::new()
+    ).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert_eq!(tiler.insert_event(Event::all_day(0.0, 24.0)), Ok((EventId(2), vec![])));
+    assert_eq!(tiler.all_day_tiles.len(), 2);
+
+    let changed_tiles = tiler.update_event(EventId(0), Event::all_day(0.0, 24.0)).unwrap();
+
+    assert!(changed_tiles.is_empty());
+    assert!(tiler.tiles.is_empty());
+    assert_eq!(tiler.all_day_tiles.iter().map(|all_day_tile| all_day_tile.lane).collect::<Vec<usize>>(), vec![0, 1, 2]);
+
+    assert_eq!(tiler.remove_event(EventId(1)), Ok(vec![]));
+    assert_eq!(
+        tiler.all_day_tiles,
+        vec![AllDayTile { original_index: 0, lane: 0 }, AllDayTile { original_index: 1, lane: 1 }]
+    );
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "serde")]
+
+use calendar_tiler::json::{self, JsonEvent};
+use calendar_tiler::{BasicTilingMethod, TilerOptions};
+use serde_json::json;
+
+#[test]
+fn tiles_are_keyed_by_id_in_original_order() {
+    let json_events: Vec<JsonEvent> = serde_json::from_value(json!([
+        { "id": "late", "start": 10.0, "end": 11.0, "payload": { "room": "A" } },
+        { "start": 9.0, "end": 10.5 }
+    ])).unwrap();
+
+    let json_tiles = json::tile_json_events(
+        json_events,
+        TilerOptions::new(0.0001),
+        BasicTilingMethod::new()
+    ).unwrap();
+
+    assert_eq!(json_tiles[0].id, json!("late"));
+    assert_eq!(json_tiles[0].tile.y, 10.0);
+    assert_eq!(json_tiles[0].payload, Some(json!({ "room": "A" })));
+    assert_eq!(json_tiles[1].id, json!(1));
+    assert_eq!(json_tiles[1].tile.y, 9.0);
+    assert_eq!(json_tiles[1].payload, None);
+}
+
+#[test]
+fn tiler_options_are_sanitized_when_deserialized() {
+    let tiler_options: TilerOptions = serde_json::from_value(json!({ "error_bound": -0.001 })).unwrap();
+
+    assert_eq!(tiler_options.error_bound, 0.001);
+}
+
+#[test]
+fn cascade_options_default_when_missing_from_json() {
+    let tiler_options: TilerOptions = serde_json::from_value(json!({ "error_bound": 0.001 })).unwrap();
+
+    assert_eq!(tiler_options.cascade_indent_step, TilerOptions::new(0.001).cascade_indent_step);
+    assert_eq!(tiler_options.cascade_max_indent, TilerOptions::new(0.001).cascade_max_indent);
+
+    let tiler_options: TilerOptions = serde_json::from_value(json!({
+        "error_bound": 0.001,
+        "cascade_indent_step": 0.2,
+        "cascade_max_indent": 0.6
+    })).unwrap();
+
+    assert_eq!(tiler_options.cascade_indent_step, 0.2);
+    assert_eq!(tiler_options.cascade_max_indent, 0.6);
+}
+
+#[test]
+fn all_day_events_carry_their_lane() {
+    let json_events: Vec<JsonEvent> = serde_json::from_value(json!([
+        { "id": "offsite", "start": 0.0, "end": 24.0, "all_day": true },
+        { "id": "standup", "start": 9.0, "end": 9.25 }
+    ])).unwrap();
+
+    let json_tiles = json::tile_json_events(
+        json_events,
+        TilerOptions::new(0.0001),
+        BasicTilingMethod::new()
+    ).unwrap();
+
+    assert_eq!(json_tiles[0].all_day_lane, Some(0));
+    assert_eq!(json_tiles[1].all_day_lane, None);
+    assert_eq!(json_tiles[1].tile.dx, 1.0);
+    assert!(serde_json::to_value(&json_tiles[1]).unwrap().get("all_day_lane").is_none());
+}
@@ -0,0 +1,98 @@
+use calendar_tiler::layout_checker::{self, LayoutViolation};
+use calendar_tiler::{BasicTilingMethod, Event, Tile, Tiler, TilerOptions};
+
+fn tile(x: f64, dx: f64, event: &Event) -> Tile {
+    Tile { x, dx, y: event.start, dy: event.end - event.start, z: 0 }
+}
+
+#[test]
+fn accepts_side_by_side_overlapping_events() {
+    let events = vec![Event::new(9.0, 10.0), Event::new(9.5, 11.0)];
+    let tiles = vec![tile(0.0, 0.5, &events[0]), tile(0.5, 0.5, &events[1])];
+
+    assert!(layout_checker::check_layout(&tiles, &events, &TilerOptions::new(0.0001)).is_empty());
+}
+
+#[test]
+fn reports_out_of_bounds_and_overlapping_tiles() {
+    let events = vec![Event::new(9.0, 10.0), Event::new(9.5, 11.0)];
+    let tiles = vec![tile(-0.25, 0.75, &events[0]), tile(0.5, 0.0, &events[1])];
+    let violations = layout_checker::check_layout(&tiles, &events, &TilerOptions::new(0.0001));
+
+    assert_eq!(violations, vec![
+        LayoutViolation::NegativeX { index: 0, x: -0.25 },
+        LayoutViolation::NonPositiveDx { index: 1, dx: 0.0 },
+    ]);
+
+    let tiles = vec![tile(0.0, 0.75, &events[0]), tile(0.5, 0.75, &events[1])];
+    let violations = layout_checker::check_layout(&tiles, &events, &TilerOptions::new(0.0001));
+
+    assert_eq!(violations, vec![
+        LayoutViolation::OverflowingDx { index: 1, x: 0.5, dx: 0.75 },
+        LayoutViolation::HorizontalOverlap { index: 0, other_index: 1 },
+    ]);
+}
+
+#[test]
+fn reports_non_finite_tiles() {
+    let events = vec![Event::new(9.0, 10.0), Event::new(9.5, 11.0)];
+    let tiles = vec![tile(f64::NAN, 0.5, &events[0]), tile(0.5, f64::INFINITY, &events[1])];
+    let violations = layout_checker::check_layout(&tiles, &events, &TilerOptions::new(0.0001));
+
+    assert_eq!(violations.len(), 2);
+    assert!(matches!(violations[0], LayoutViolation::NonFiniteTile { index: 0, x, dx } if x.is_nan() && dx == 0.5));
+    assert_eq!(violations[1], LayoutViolation::NonFiniteTile { index: 1, x: 0.5, dx: f64::INFINITY });
+}
+
+#[test]
+fn ignores_events_that_only_touch() {
+    let events = vec![Event::new(9.0, 10.0), Event::new(10.0, 11.0)];
+    let tiles = vec![tile(0.0, 1.0, &events[0]), tile(0.0, 1.0, &events[1])];
+
+    assert!(layout_checker::check_layout(&tiles, &events, &TilerOptions::new(0.0001)).is_empty());
+}
+
+#[test]
+fn reports_tiles_that_do_not_match_their_events() {
+    let events = vec![Event::new(9.0, 10.0)];
+    let tiles = vec![Tile { x: 0.0, dx: 1.0, y: 9.0, dy: 2.0, z: 0 }];
+    let violations = layout_checker::check_layout(&tiles, &events, &TilerOptions::new(0.0001));
+
+    assert_eq!(violations, vec![
+        LayoutViolation::TimeMismatch { index: 0, tile: tiles[0], event: events[0] },
+    ]);
+}
+
+#[test]
+fn basic_tiling_produces_a_valid_layout() {
+    let mut tiler = Tiler::new(
+        vec![
+            Event::new(9.0, 10.0),
+            Event::new(9.0, 9.5),
+            Event::new(9.5, 12.0),
+            Event::new(13.0, 14.0),
+        ],
+        TilerOptions::new(0.0001),
+        BasicTilingMethod::new()
+    ).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert_eq!(tiler.check_layout(), vec![]);
+
+    layout_checker::debug_assert_layout(
+        &tiler.get_tiles_in_original_order(),
+        &tiler.events,
+        &tiler.tiler_options
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "tiles 0 and 1 overlap")]
+fn debug_assert_layout_panics_on_violations() {
+    let events = vec![Event::new(9.0, 10.0), Event::new(9.5, 11.0)];
+    let tiles = vec![tile(0.0, 1.0, &events[0]), tile(0.0, 1.0, &events[1])];
+
+    layout_checker::debug_assert_layout(&tiles, &events, &TilerOptions::new(0.0001));
+}
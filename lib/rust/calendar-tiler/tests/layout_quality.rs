@@ -0,0 +1,151 @@
+use calendar_tiler::{
+    get_tiling_method,
+    get_tiling_method_with_objective,
+    score_layout,
+    AutoTilingMethod,
+    Event,
+    LayoutObjective,
+    LayoutQuality,
+    Tile,
+    Tiler,
+    TilerError,
+    TilerOptions,
+    TilingMethod,
+    TilingMethodName,
+};
+use std::cmp::Ordering;
+
+fn tile(x: f64, dx: f64, event: &Event) -> Tile {
+    Tile { x, dx, y: event.start, dy: event.end - event.start, z: 0 }
+}
+
+fn quality(min_width: f64, area_utilization: f64, width_variance: f64, narrow_tile_count: usize) -> LayoutQuality {
+    LayoutQuality { min_width, area_utilization, width_variance, narrow_tile_count }
+}
+
+#[test]
+fn scores_each_measure_of_a_layout() {
+    let events = vec![
+        Event::new(9.0, 11.0),
+        Event::new(9.0, 10.0),
+        Event::new(12.0, 13.0),
+    ];
+    let tiles = vec![
+        tile(0.0, 0.9, &events[0]),
+        tile(0.9, 0.1, &events[1]),
+        tile(0.0, 1.0, &events[2]),
+    ];
+
+    let quality = score_layout(&tiles, &events, &TilerOptions::new(0.0001));
+
+    assert_eq!(quality.min_width, 0.1);
+    assert!((quality.area_utilization - 2.9 / 3.0).abs() < 0.0001);
+    assert!((quality.width_variance - 0.32 / 3.0).abs() < 0.0001);
+    assert_eq!(quality.narrow_tile_count, 1);
+}
+
+#[test]
+fn scores_an_empty_day_as_perfect() {
+    assert_eq!(score_layout(&[], &[], &TilerOptions::new(0.0001)), quality(1.0, 1.0, 0.0, 0));
+}
+
+#[test]
+fn ranks_by_the_objective_before_breaking_ties() {
+    let wide = quality(0.5, 0.8, 0.1, 0);
+    let full = quality(0.25, 1.0, 0.0, 1);
+
+    assert_eq!(wide.compare(&full, LayoutObjective::MinWidth, 0.0001), Ordering::Greater);
+    assert_eq!(wide.compare(&full, LayoutObjective::AreaUtilization, 0.0001), Ordering::Less);
+    assert_eq!(wide.compare(&full, LayoutObjective::WidthVariance, 0.0001), Ordering::Less);
+    assert_eq!(wide.compare(&full, LayoutObjective::NarrowTileCount, 0.0001), Ordering::Greater);
+    assert_eq!(
+        wide.compare(&quality(0.5, 0.9, 0.1, 0), LayoutObjective::MinWidth, 0.0001),
+        Ordering::Less
+    );
+}
+
+fn score_with<T: TilingMethod>(events: &[Event], tiling_method: T) -> LayoutQuality {
+    let mut tiler = Tiler::new(events.to_vec(), TilerOptions::new(0.0001), tiling_method).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert!(tiler.check_layout().is_empty());
+
+    tiler.score_layout()
+}
+
+#[test]
+fn auto_tiling_keeps_the_best_scoring_layout() {
+    // Basic gives the long event a third of the width all day, while the
+    // methods that fill free space let it widen once the short ones end.
+    let events = vec![
+        Event::new(9.0, 12.0),
+        Event::new(9.0, 10.0),
+        Event::new(9.5, 10.0),
+        Event::new(11.0, 11.5),
+    ];
+
+    for &objective in [LayoutObjective::MinWidth, LayoutObjective::AreaUtilization].iter() {
+        let quality = score_with(&events, AutoTilingMethod::with_objective(objective));
+
+        for &tiling_method_name in TilingMethodName::ALL.iter() {
+            if tiling_method_name == TilingMethodName::Cascade {
+                continue;
+            }
+
+            let other_quality = score_with(&events, get_tiling_method(tiling_method_name));
+
+            assert_ne!(quality.compare(&other_quality, objective, 0.0001), Ordering::Less, "{}", tiling_method_name);
+        }
+    }
+
+    let basic_quality = score_with(&events, get_tiling_method(TilingMethodName::Basic));
+    let auto_quality = score_with(&events, AutoTilingMethod::with_objective(LayoutObjective::AreaUtilization));
+
+    assert!(auto_quality.area_utilization > basic_quality.area_utilization);
+}
+
+#[test]
+fn objectives_are_chosen_by_name() {
+    for &objective in LayoutObjective::ALL.iter() {
+        assert_eq!(objective.name().parse::<LayoutObjective>(), Ok(objective));
+    }
+
+    assert!("widest".parse::<LayoutObjective>().is_err());
+
+    let events = vec![
+        Event::new(9.0, 12.0),
+        Event::new(9.0, 10.0),
+        Event::new(9.5, 10.0),
+        Event::new(11.0, 11.5),
+    ];
+    let objective = LayoutObjective::WidthVariance;
+
+    assert_eq!(
+        score_with(&events, get_tiling_method_with_objective(TilingMethodName::Auto, objective)),
+        score_with(&events, AutoTilingMethod::with_objective(objective))
+    );
+    assert_eq!(
+        score_with(&events, get_tiling_method_with_objective(TilingMethodName::Basic, objective)),
+        score_with(&events, get_tiling_method(TilingMethodName::Basic))
+    );
+}
+
+#[test]
+fn auto_tiling_reports_failing_methods_only_when_strict() {
+    // No layout gives both overlapping events more than half the width, so
+    // the optimized method fails while the others ignore `min_width`.
+    let events = vec![
+        Event::new(9.0, 10.0),
+        Event::new(9.5, 10.5),
+    ];
+    let tiler_options = TilerOptions { min_width: 0.6, ..TilerOptions::new(0.0001) };
+    let mut tiler = Tiler::new(events.clone(), tiler_options, AutoTilingMethod::new()).unwrap();
+
+    assert_eq!(tiler.tile(), Ok(()));
+    assert_eq!(tiler.score_layout().min_width, 0.5);
+
+    let mut tiler = Tiler::new(events, tiler_options, AutoTilingMethod::strict()).unwrap();
+
+    assert_eq!(tiler.tile(), Err(TilerError::UnsatisfiableMinWidth { min_width: 0.6, max_min_width: 0.5 }));
+}
@@ -0,0 +1,85 @@
+use calendar_tiler::week_tiler::SECONDS_PER_DAY;
+use calendar_tiler::{layout_month, Event, InvalidEventReason, MonthLayout, TilerError, TilerOptions};
+
+// The Monday midnight that opens the grid, in seconds since the Unix epoch.
+const MONTH_START: f64 = 1_704_067_200.0;
+
+fn days(start_day: f64, end_day: f64) -> Event {
+    Event::all_day(MONTH_START + start_day * SECONDS_PER_DAY, MONTH_START + end_day * SECONDS_PER_DAY)
+}
+
+fn layout(events: &[Event], max_rows: Option<usize>) -> MonthLayout {
+    layout_month(events, MONTH_START, 5, max_rows, &TilerOptions::new(0.0001)).unwrap()
+}
+
+// (original index, week, row, start cell, end cell) of every bar.
+fn bar_cells(month_layout: &MonthLayout) -> Vec<(usize, usize, usize, usize, usize)> {
+    month_layout.bars.iter()
+        .map(|bar| (bar.original_index, bar.week_index, bar.row_index, bar.start_cell, bar.end_cell))
+        .collect()
+}
+
+#[test]
+fn stacks_overlapping_bars_longest_first() {
+    let month_layout = layout(&[
+        days(1.0, 2.0),
+        days(1.0, 4.0),
+        days(2.0, 3.0),
+        days(4.0, 5.0),
+    ], None);
+
+    assert_eq!(bar_cells(&month_layout), vec![
+        (1, 0, 0, 1, 4),
+        (3, 0, 0, 4, 5),
+        (0, 0, 1, 1, 2),
+        (2, 0, 1, 2, 3),
+    ]);
+}
+
+#[test]
+fn splits_bars_at_week_rows() {
+    let month_layout = layout(&[days(5.5, 15.25)], None);
+    let continuations: Vec<(bool, bool)> = month_layout.bars.iter()
+        .map(|bar| (bar.continues_before, bar.continues_after))
+        .collect();
+
+    assert_eq!(bar_cells(&month_layout), vec![(0, 0, 0, 5, 7), (0, 1, 0, 0, 7), (0, 2, 0, 0, 2)]);
+    assert_eq!(continuations, vec![(false, true), (true, true), (true, false)]);
+}
+
+#[test]
+fn counts_hidden_bars_in_every_cell_they_cover() {
+    let month_layout = layout(&[
+        days(0.0, 3.0),
+        days(0.0, 2.0),
+        days(1.0, 2.0),
+        days(2.0, 3.0),
+    ], Some(2));
+
+    let hidden: Vec<usize> = month_layout.bars.iter()
+        .filter(|bar| bar.hidden)
+        .map(|bar| bar.original_index)
+        .collect();
+
+    assert_eq!(hidden, vec![2]);
+    assert_eq!(month_layout.overflow_counts[0], [0, 1, 0, 0, 0, 0, 0]);
+    assert!(month_layout.overflow_counts[1 ..].iter().all(|counts| counts.iter().all(|&count| count == 0)));
+}
+
+#[test]
+fn drops_events_outside_the_grid() {
+    let month_layout = layout(&[days(-3.0, -1.0), days(35.0, 36.0), days(-1.0, 1.0)], None);
+
+    assert_eq!(bar_cells(&month_layout), vec![(2, 0, 0, 0, 1)]);
+    assert!(month_layout.bars[0].continues_before);
+}
+
+#[test]
+fn rejects_invalid_events() {
+    let result = layout_month(&[days(2.0, 1.0)], MONTH_START, 5, None, &TilerOptions::new(0.0001));
+
+    assert_eq!(result.err(), Some(TilerError::InvalidEvent {
+        original_index: 0,
+        reason: InvalidEventReason::EndBeforeStart
+    }));
+}
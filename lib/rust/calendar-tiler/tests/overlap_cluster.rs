@@ -0,0 +1,61 @@
+use calendar_tiler::{BasicTilingMethod, Event, OverlapCluster, Tiler, TilerOptions};
+
+fn get_overlap_clusters(events: Vec<Event>, error_bound: f64) -> Vec<OverlapCluster> {
+    Tiler::new(events, TilerOptions::new(error_bound), BasicTilingMethod::new())
+        .unwrap()
+        .get_overlap_clusters()
+}
+
+#[test]
+fn groups_connected_overlaps_with_their_span_and_concurrency() {
+    let clusters = get_overlap_clusters(vec![
+        Event::new(13.0, 14.0),
+        Event::new(9.0, 10.0),
+        Event::new(9.5, 11.0),
+        Event::new(10.5, 12.0),
+        Event::new(9.0, 9.75),
+    ], 0.0001);
+
+    assert_eq!(clusters, vec![
+        OverlapCluster {
+            start: 9.0,
+            end: 12.0,
+            sorted_indices: 0 .. 4,
+            original_indices: vec![1, 4, 2, 3],
+            max_concurrency: 3,
+        },
+        OverlapCluster {
+            start: 13.0,
+            end: 14.0,
+            sorted_indices: 4 .. 5,
+            original_indices: vec![0],
+            max_concurrency: 1,
+        },
+    ]);
+}
+
+#[test]
+fn splits_touching_events_within_the_error_bound() {
+    let events = vec![Event::new(9.0, 10.0), Event::new(9.95, 11.0)];
+
+    assert_eq!(get_overlap_clusters(events.clone(), 0.0001).len(), 1);
+    assert_eq!(get_overlap_clusters(events, 0.1).len(), 2);
+}
+
+#[test]
+fn counts_touching_events_as_not_overlapping() {
+    let clusters = get_overlap_clusters(vec![
+        Event::new(9.0, 10.0),
+        Event::new(9.0, 12.0),
+        Event::new(10.0, 11.0),
+        Event::new(9.0, 9.0),
+    ], 0.0001);
+
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].max_concurrency, 2);
+}
+
+#[test]
+fn finds_no_clusters_in_an_empty_day() {
+    assert!(get_overlap_clusters(vec![], 0.0001).is_empty());
+}
@@ -0,0 +1,51 @@
+use calendar_tiler::simplex::{self, Constraint, SimplexError};
+
+const ERROR_BOUND: f64 = 0.000001;
+
+fn constraint(coefficients: &[(usize, f64)], bound: f64) -> Constraint {
+    Constraint { coefficients: coefficients.to_vec(), bound }
+}
+
+fn assert_close(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+
+    for (actual, expected) in actual.iter().zip(expected.iter()) {
+        assert!((actual - expected).abs() < ERROR_BOUND, "{:?} != {:?}", actual, expected);
+    }
+}
+
+#[test]
+fn maximizes_over_the_feasible_region() {
+    // 3x + 5y subject to x <= 4, 2y <= 12 and 3x + 2y <= 18 peaks at (2, 6).
+    let solution = simplex::maximize(&[3.0, 5.0], &[
+        constraint(&[(0, 1.0)], 4.0),
+        constraint(&[(1, 2.0)], 12.0),
+        constraint(&[(0, 3.0), (1, 2.0)], 18.0),
+    ]).unwrap();
+
+    assert_close(&solution, &[2.0, 6.0]);
+}
+
+#[test]
+fn negative_bounds_are_met_before_optimizing() {
+    // x + y >= 2 written as -x - y <= -2, with x <= 1 and y <= 3, maximizing -y.
+    let solution = simplex::maximize(&[0.0, -1.0], &[
+        constraint(&[(0, -1.0), (1, -1.0)], -2.0),
+        constraint(&[(0, 1.0)], 1.0),
+        constraint(&[(1, 1.0)], 3.0),
+    ]).unwrap();
+
+    assert_close(&solution, &[1.0, 1.0]);
+}
+
+#[test]
+fn reports_infeasible_and_unbounded_programs() {
+    assert_eq!(
+        simplex::maximize(&[1.0], &[constraint(&[(0, 1.0)], 1.0), constraint(&[(0, -1.0)], -2.0)]),
+        Err(SimplexError::Infeasible)
+    );
+    assert_eq!(
+        simplex::maximize(&[1.0, 1.0], &[constraint(&[(0, 1.0), (1, -1.0)], 1.0)]),
+        Err(SimplexError::Unbounded)
+    );
+}
@@ -0,0 +1,141 @@
+//! Golden snapshots of every fixture day in `tests/fixtures/days`, tiled with
+//! every tiling method. Expected tiles live in `tests/fixtures/snapshots` as
+//! `<day>.<method>.json`. After an intended layout change, re-bless them with
+//!
+//! ```text
+//! CALENDAR_TILER_BLESS=1 cargo test --test snapshots
+//! ```
+#![cfg(feature = "serde")]
+
+use calendar_tiler::json::{self, JsonEvent, JsonTile};
+use calendar_tiler::{get_tiling_method, Event, Tile, TilerOptions, TilingMethodName};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BLESS_VAR: &str = "CALENDAR_TILER_BLESS";
+const ERROR_BOUND: f64 = 0.0001;
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures")
+}
+
+fn read_days() -> Vec<(String, Vec<JsonEvent>)> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(fixtures_dir().join("days"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect();
+
+    paths.sort();
+
+    paths.into_iter().map(|path| {
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let json_events = serde_json::from_str(&fs::read_to_string(&path).unwrap())
+            .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+
+        (name, json_events)
+    }).collect()
+}
+
+fn diff_tile(
+    id: &str,
+    expected: &Tile,
+    actual: &Tile,
+    differences: &mut Vec<String>
+) {
+    let fields = [
+        ("x", expected.x, actual.x),
+        ("dx", expected.dx, actual.dx),
+        ("y", expected.y, actual.y),
+        ("dy", expected.dy, actual.dy),
+        ("z", expected.z as f64, actual.z as f64),
+    ];
+
+    for (field, expected, actual) in fields.iter() {
+        if (expected - actual).abs() > ERROR_BOUND {
+            differences.push(format!("  {} {}: expected {:.4}, got {:.4}", id, field, expected, actual));
+        }
+    }
+}
+
+fn diff_tiles(expected: &[JsonTile], actual: &[JsonTile]) -> Vec<String> {
+    let mut differences: Vec<String> = vec![];
+
+    if expected.len() != actual.len() {
+        differences.push(format!("  expected {} tiles, got {}", expected.len(), actual.len()));
+
+        return differences;
+    }
+
+    for (expected, actual) in expected.iter().zip(actual.iter()) {
+        if expected.id != actual.id {
+            differences.push(format!("  expected tile {}, got {}", expected.id, actual.id));
+        } else {
+            diff_tile(&expected.id.to_string(), &expected.tile, &actual.tile, &mut differences);
+        }
+    }
+
+    differences
+}
+
+#[test]
+fn fixture_days_match_their_snapshots() {
+    let bless = env::var_os(BLESS_VAR).is_some();
+    let snapshots_dir = fixtures_dir().join("snapshots");
+    let mut failures: Vec<String> = vec![];
+
+    if bless {
+        fs::create_dir_all(&snapshots_dir).unwrap();
+    }
+
+    for (day, json_events) in read_days() {
+        let events: Vec<Event> = json_events.iter().map(|json_event| json_event.event).collect();
+
+        for tiling_method_name in TilingMethodName::ALL.iter() {
+            let name = format!("{}.{}", day, tiling_method_name.name());
+            let tiler_options = TilerOptions::new(ERROR_BOUND);
+            let actual = json::tile_json_events(
+                json_events.clone(),
+                tiler_options,
+                get_tiling_method(*tiling_method_name)
+            ).unwrap();
+
+            let tiles: Vec<Tile> = actual.iter().map(|json_tile| json_tile.tile).collect();
+            let violations = calendar_tiler::check_layout(&tiles, &events, &tiler_options);
+
+            if !violations.is_empty() {
+                failures.push(format!("{} is not a valid layout: {:?}", name, violations));
+                continue;
+            }
+
+            let path = snapshots_dir.join(format!("{}.json", name));
+
+            if bless {
+                fs::write(&path, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+                continue;
+            }
+
+            let expected: Vec<JsonTile> = match fs::read_to_string(&path) {
+                Ok(contents) => serde_json::from_str(&contents).unwrap(),
+                Err(_) => {
+                    failures.push(format!("{} has no snapshot", name));
+                    continue;
+                }
+            };
+
+            let differences = diff_tiles(&expected, &actual);
+
+            if !differences.is_empty() {
+                failures.push(format!("{} moved:\n{}", name, differences.join("\n")));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nRe-bless intended changes with {}=1 cargo test --test snapshots",
+        failures.join("\n\n"),
+        BLESS_VAR
+    );
+}
@@ -0,0 +1,199 @@
+use calendar_tiler::tiler;
+use calendar_tiler::{
+    AllDayTile,
+    BasicTilingMethod,
+    Event,
+    FallbackReason,
+    IntervalColoringTilingMethod,
+    InvalidEventReason,
+    OptimizedTilingMethod,
+    Tiler,
+    TilerError,
+    TilerOptions,
+};
+
+fn new_tiler(events: Vec<Event>) -> Result<Tiler<BasicTilingMethod>, TilerError> {
+    Tiler::new(events, TilerOptions::new(0.0001), BasicTilingMethod::new())
+}
+
+#[test]
+fn rejects_end_before_start() {
+    let result = new_tiler(vec![
+        Event::new(9.0, 10.0),
+        Event::new(11.0, 10.0),
+    ]);
+
+    assert_eq!(result.err(), Some(TilerError::InvalidEvent {
+        original_index: 1,
+        reason: InvalidEventReason::EndBeforeStart
+    }));
+}
+
+#[test]
+fn rejects_non_finite_times() {
+    let result = new_tiler(vec![Event::new(f64::NAN, 10.0)]);
+
+    assert_eq!(result.err(), Some(TilerError::InvalidEvent {
+        original_index: 0,
+        reason: InvalidEventReason::NonFiniteStart
+    }));
+
+    let result = new_tiler(vec![Event::new(9.0, f64::INFINITY)]);
+
+    assert_eq!(result.err(), Some(TilerError::InvalidEvent {
+        original_index: 0,
+        reason: InvalidEventReason::NonFiniteEnd
+    }));
+}
+
+#[test]
+fn tiles_an_empty_day() {
+    let mut tiler = new_tiler(vec![]).unwrap();
+
+    assert_eq!(tiler.tile(), Ok(()));
+    assert!(tiler.tiles.is_empty());
+}
+
+#[test]
+fn sorts_starts_within_the_error_bound_longest_first() {
+    let tiler = new_tiler(vec![
+        Event::new(9.00002, 10.0),
+        Event::new(9.00008, 11.0),
+        Event::new(8.5, 9.5),
+    ]).unwrap();
+    let original_indices: Vec<usize> = tiler.sorted_events.iter().map(|sorted_event| sorted_event.original_index).collect();
+
+    assert_eq!(original_indices, vec![2, 1, 0]);
+}
+
+#[test]
+fn generates_columns_with_each_event_once() {
+    let tiler = new_tiler(vec![
+        Event::new(9.0, 10.0),
+        Event::new(9.5, 11.0),
+        Event::new(10.5, 12.0),
+        Event::new(11.5, 12.0),
+        Event::new(13.0, 14.0),
+    ]).unwrap();
+
+    let columns = tiler::generate_columns(&tiler.sorted_events, &tiler.tiler_options);
+    let column_indices: Vec<Vec<usize>> = columns.iter()
+        .map(|column| column.iter().map(|sorted_event| sorted_event.original_index).collect())
+        .collect();
+
+    assert_eq!(column_indices, vec![vec![0, 2, 4], vec![1, 3]]);
+}
+
+#[test]
+fn basic_tiling_gives_a_lone_event_the_full_width() {
+    let mut tiler = new_tiler(vec![Event::new(9.0, 10.0)]).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert_eq!((tiler.tiles[0].x, tiler.tiles[0].dx), (0.0, 1.0));
+}
+
+#[test]
+fn optimized_tiling_rejects_an_unsatisfiable_min_width() {
+    let events = vec![
+        Event::new(9.0, 10.0),
+        Event::new(9.5, 10.5),
+    ];
+    let tiler_options = TilerOptions { min_width: 0.6, ..TilerOptions::new(0.0001) };
+    let mut tiler = Tiler::new(events, tiler_options, OptimizedTilingMethod::new()).unwrap();
+
+    assert_eq!(tiler.tile(), Err(TilerError::UnsatisfiableMinWidth { min_width: 0.6, max_min_width: 0.5 }));
+
+    tiler.tiler_options.min_width = 0.5;
+
+    assert_eq!(tiler.tile(), Ok(()));
+    assert_eq!(tiler.tiles[1].x + tiler.tiles[1].dx, 1.0);
+}
+
+#[test]
+fn optimized_tiling_keeps_a_binding_min_width_over_area() {
+    // Area alone would give the long event the whole width.
+    let events = vec![
+        Event::new(9.0, 17.0),
+        Event::new(9.0, 10.0),
+    ];
+    let tiler_options = TilerOptions { min_width: 0.5, ..TilerOptions::new(0.0001) };
+    let mut tiler = Tiler::new(events, tiler_options, OptimizedTilingMethod::new()).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert_eq!(tiler.tiles.iter().map(|tile| tile.dx).collect::<Vec<f64>>(), vec![0.5, 0.5]);
+}
+
+#[test]
+fn optimized_tiling_keeps_min_width_in_clusters_too_large_to_solve() {
+    // Ten events are in progress at once throughout, so a tenth is the widest
+    // minimum width.
+    let events: Vec<Event> = (0 .. 80).map(|i| Event::new(i as f64 * 0.25, i as f64 * 0.25 + 2.5)).collect();
+    let tiler_options = TilerOptions { min_width: 0.1, ..TilerOptions::new(0.0001) };
+    let mut tiler = Tiler::new(events.clone(), tiler_options, OptimizedTilingMethod::new()).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert!(tiler.tiles.iter().all(|tile| tile.dx >= 0.1 - 0.0001));
+    assert!(tiler.check_layout().is_empty());
+
+    let mut tiler = Tiler::new(events, tiler_options, OptimizedTilingMethod::strict()).unwrap();
+
+    assert_eq!(tiler.tile(), Err(TilerError::OptimizationFallback {
+        sorted_index: 0,
+        number_of_events: 80,
+        reason: FallbackReason::ClusterTooLarge
+    }));
+}
+
+#[test]
+fn optimized_tiling_lets_touching_events_share_a_column() {
+    let events = vec![
+        Event::new(6.0, 7.75),
+        Event::new(7.5, 7.75),
+        Event::new(5.75, 7.5),
+    ];
+    let mut tiler = Tiler::new(events, TilerOptions::new(0.0001), OptimizedTilingMethod::new()).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert!(tiler.tiles.iter().all(|tile| (tile.dx - 0.5).abs() < 0.0001));
+    assert!(tiler.check_layout().is_empty());
+}
+
+#[test]
+fn interval_coloring_lets_touching_events_share_a_column() {
+    let events = vec![
+        Event::new(9.0, 10.0),
+        Event::new(9.0, 12.0),
+        Event::new(10.0, 11.0),
+    ];
+    let mut tiler = Tiler::new(events, TilerOptions::new(0.0001), IntervalColoringTilingMethod::new()).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert!(tiler.tiles.iter().all(|tile| (tile.dx - 0.5).abs() < 0.0001));
+    assert!(tiler.check_layout().is_empty());
+}
+
+#[test]
+fn all_day_events_get_lanes_instead_of_squeezing_the_day() {
+    let mut tiler = new_tiler(vec![
+        Event::all_day(0.0, 24.0),
+        Event::new(9.0, 10.0),
+        Event::all_day(0.0, 24.0),
+    ]).unwrap();
+
+    let day_layout = tiler.tile_day().unwrap();
+
+    assert_eq!(day_layout.tiles.len(), 1);
+    assert_eq!(day_layout.tiles[0].0, 1);
+    assert_eq!((day_layout.tiles[0].1.x, day_layout.tiles[0].1.dx), (0.0, 1.0));
+    assert_eq!(day_layout.all_day_tiles, vec![
+        AllDayTile { original_index: 0, lane: 0 },
+        AllDayTile { original_index: 2, lane: 1 },
+    ]);
+    assert_eq!(tiler.get_tiles_in_original_order().len(), 3);
+    assert!(tiler.check_layout().is_empty());
+}
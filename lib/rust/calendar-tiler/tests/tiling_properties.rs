@@ -0,0 +1,215 @@
+use calendar_tiler::{
+    get_tiling_method, Event, SortedEvent, Tile, Tiler, TilerOptions, TilingMethod, TilingMethodName, AutoTilingMethod,
+    BasicTilingMethod, CascadeTilingMethod, ExpandRightTilingMethod, IntervalColoringTilingMethod, OptimizedTilingMethod,
+    SpaceFillingTilingMethod, TimeRespectiveTilingMethod,
+};
+use calendar_tiler::overlap_cluster;
+use proptest::prelude::*;
+
+const ERROR_BOUND: f64 = 0.0001;
+const QUARTERS_PER_DAY: u32 = 96;
+const MAX_NUMBER_OF_EVENTS: usize = 40;
+
+// Quarter-hour events make identical starts, touching events and zero-duration
+// events common, which is where the tiling methods have broken before.
+fn quarter_hour_event() -> impl Strategy<Value = Event> {
+    (0 .. QUARTERS_PER_DAY, 0 .. 16_u32).prop_map(|(start, duration)| {
+        let end = (start + duration).min(QUARTERS_PER_DAY);
+
+        Event::new(start as f64 / 4.0, end as f64 / 4.0)
+    })
+}
+
+fn arbitrary_event() -> impl Strategy<Value = Event> {
+    (0.0 .. 24.0_f64, 0.0 .. 4.0_f64).prop_map(|(start, duration)| {
+        Event::new(start, (start + duration).min(24.0))
+    })
+}
+
+fn events() -> impl Strategy<Value = Vec<Event>> {
+    prop::collection::vec(
+        prop_oneof![3 => quarter_hour_event(), 1 => arbitrary_event()],
+        0 ..= MAX_NUMBER_OF_EVENTS
+    )
+}
+
+fn assert_valid_layout<T: TilingMethod>(
+    events: Vec<Event>,
+    tiling_method: T
+) -> Result<(), TestCaseError> {
+    let mut tiler = Tiler::new(events, TilerOptions::new(ERROR_BOUND), tiling_method).unwrap();
+
+    tiler.tile().unwrap();
+
+    let violations = tiler.check_layout();
+
+    prop_assert!(violations.is_empty(), "{:?}", violations);
+
+    Ok(())
+}
+
+fn tile<T: TilingMethod>(events: Vec<Event>, tiling_method: T) -> Vec<Tile> {
+    let mut tiler = Tiler::new(events, TilerOptions::new(ERROR_BOUND), tiling_method).unwrap();
+
+    tiler.tile().unwrap();
+    tiler.get_tiles_in_original_order()
+}
+
+fn min_width(tiles: &[Tile]) -> f64 {
+    tiles.iter().map(|tile| tile.dx).fold(1.0, f64::min)
+}
+
+// Of any events that overlap at once, the one that starts last overlaps every
+// other, so counting the earlier events each one overlaps, pair by pair, finds
+// the most events that overlap at once.
+fn get_concurrency_by_pairs(sorted_events: &[SortedEvent]) -> usize {
+    sorted_events.iter().enumerate().map(|(i, sorted_event)| {
+        1 + sorted_events[.. i].iter()
+                               .filter(|other| other.event.overlaps(&sorted_event.event, ERROR_BOUND))
+                               .count()
+    }).max().unwrap_or(0)
+}
+
+proptest! {
+    #[test]
+    fn basic_tiling_produces_valid_layouts(events in events()) {
+        assert_valid_layout(events, BasicTilingMethod::new())?;
+    }
+
+    #[test]
+    fn space_filling_tiling_produces_valid_layouts(events in events()) {
+        assert_valid_layout(events, SpaceFillingTilingMethod::new())?;
+    }
+
+    #[test]
+    fn time_respective_tiling_produces_valid_layouts(events in events()) {
+        assert_valid_layout(events, TimeRespectiveTilingMethod::new())?;
+    }
+
+    #[test]
+    fn interval_coloring_tiling_produces_valid_layouts(events in events()) {
+        assert_valid_layout(events, IntervalColoringTilingMethod::new())?;
+    }
+
+    #[test]
+    fn expand_right_tiling_produces_valid_layouts(events in events()) {
+        assert_valid_layout(events, ExpandRightTilingMethod::new())?;
+    }
+
+    #[test]
+    fn cascade_tiling_produces_valid_layouts(events in events()) {
+        assert_valid_layout(events, CascadeTilingMethod::new())?;
+    }
+
+    #[test]
+    fn cascade_tiling_never_indents_past_the_max_indent(
+        events in events(),
+        cascade_indent_step in 0.0 .. 0.5_f64,
+        cascade_max_indent in 0.0 .. 1.5_f64
+    ) {
+        let tiler_options = TilerOptions { cascade_indent_step, cascade_max_indent, ..TilerOptions::new(ERROR_BOUND) };
+        let mut tiler = Tiler::new(events, tiler_options, CascadeTilingMethod::new()).unwrap();
+
+        tiler.tile().unwrap();
+
+        for tile in tiler.tiles.iter() {
+            prop_assert!(tile.x <= cascade_max_indent.min(0.9) + ERROR_BOUND, "{:?}", tile);
+            prop_assert!((tile.x + tile.dx - 1.0).abs() <= ERROR_BOUND, "{:?}", tile);
+        }
+
+        prop_assert!(tiler.check_layout().is_empty());
+    }
+
+    #[test]
+    fn optimized_tiling_produces_valid_layouts(events in events()) {
+        assert_valid_layout(events, OptimizedTilingMethod::new())?;
+    }
+
+    #[test]
+    fn optimized_tiling_is_at_least_as_wide_as_space_filling(events in events()) {
+        // The optimized method widens each overlap cluster as far as its order
+        // allows, and that order lets touching events share a column where
+        // space-filling keeps them apart. The two can order a cluster
+        // differently, so only the narrowest tiles are compared, not the area.
+        let mut space_filling_tiler = Tiler::new(events.clone(), TilerOptions::new(ERROR_BOUND), SpaceFillingTilingMethod::new()).unwrap();
+        let mut optimized_tiler = Tiler::new(events, TilerOptions::new(ERROR_BOUND), OptimizedTilingMethod::new()).unwrap();
+
+        space_filling_tiler.tile().unwrap();
+        optimized_tiler.tile().unwrap();
+
+        for range in overlap_cluster::get_cluster_ranges(&optimized_tiler.sorted_events, &optimized_tiler.tiler_options) {
+            let space_filling_tiles = &space_filling_tiler.tiles[range.clone()];
+            let optimized_tiles = &optimized_tiler.tiles[range];
+
+            prop_assert!(min_width(optimized_tiles) >= min_width(space_filling_tiles) - ERROR_BOUND);
+        }
+    }
+
+    #[test]
+    fn auto_tiling_is_never_narrower_than_the_methods_it_picks_from(events in events()) {
+        let auto_min_width = min_width(&tile(events.clone(), AutoTilingMethod::new()));
+
+        for &tiling_method_name in TilingMethodName::ALL.iter() {
+            if tiling_method_name != TilingMethodName::Cascade {
+                let tiles = tile(events.clone(), get_tiling_method(tiling_method_name));
+
+                prop_assert!(auto_min_width >= min_width(&tiles) - ERROR_BOUND, "{}", tiling_method_name);
+            }
+        }
+    }
+
+    #[test]
+    fn interval_coloring_splits_each_cluster_by_its_concurrency(events in events()) {
+        let mut tiler = Tiler::new(
+            events,
+            TilerOptions::new(ERROR_BOUND),
+            IntervalColoringTilingMethod::new()
+        ).unwrap();
+
+        tiler.tile().unwrap();
+
+        for cluster in tiler.get_overlap_clusters() {
+            let concurrency = get_concurrency_by_pairs(&tiler.sorted_events[cluster.sorted_indices.clone()]);
+
+            for sorted_index in cluster.sorted_indices.clone() {
+                prop_assert_eq!(tiler.tiles[sorted_index].dx, 1.0 / concurrency as f64);
+            }
+        }
+    }
+}
+
+fn assert_valid_layout_for_all_methods(events: Vec<Event>) {
+    for tiling_method_name in TilingMethodName::ALL.iter() {
+        assert_valid_layout(events.clone(), get_tiling_method(*tiling_method_name)).unwrap();
+    }
+}
+
+#[test]
+fn identical_start_times_produce_valid_layouts() {
+    assert_valid_layout_for_all_methods(vec![
+        Event::new(9.0, 10.0),
+        Event::new(9.0, 10.0),
+        Event::new(9.0, 9.5),
+        Event::new(9.0, 11.0),
+    ]);
+}
+
+#[test]
+fn touching_events_produce_valid_layouts() {
+    assert_valid_layout_for_all_methods(vec![
+        Event::new(9.0, 10.0),
+        Event::new(10.0, 11.0),
+        Event::new(9.5, 10.0),
+        Event::new(10.0, 10.5),
+    ]);
+}
+
+#[test]
+fn zero_duration_events_produce_valid_layouts() {
+    assert_valid_layout_for_all_methods(vec![
+        Event::new(9.0, 9.0),
+        Event::new(9.0, 9.0),
+        Event::new(8.0, 10.0),
+        Event::new(10.0, 10.0),
+    ]);
+}
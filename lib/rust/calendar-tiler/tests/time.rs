@@ -0,0 +1,98 @@
+#![cfg(feature = "timezone")]
+
+use calendar_tiler::week_tiler::SECONDS_PER_HOUR;
+use calendar_tiler::{BasicTilingMethod, DisplayDay, Event, TimeError, Tiler, TilerOptions};
+
+fn day(date: &str) -> DisplayDay {
+    DisplayDay::new(date, "America/New_York").unwrap()
+}
+
+fn at(day: &DisplayDay, start_hours: f64, end_hours: f64) -> Event {
+    Event::new(day.start + start_hours * SECONDS_PER_HOUR, day.start + end_hours * SECONDS_PER_HOUR)
+}
+
+#[test]
+fn days_run_from_local_midnight_to_local_midnight() {
+    let spring_forward = day("2024-03-10");
+    let fall_back = day("2024-11-03");
+
+    assert_eq!((spring_forward.start, spring_forward.end), (1_710_046_800.0, 1_710_129_600.0));
+    assert_eq!(spring_forward.get_hours(), 23.0);
+    assert_eq!((fall_back.start, fall_back.end), (1_730_606_400.0, 1_730_696_400.0));
+    assert_eq!(fall_back.get_hours(), 25.0);
+    assert_eq!(DisplayDay::new("2024-11-03", "Europe/London").unwrap().get_hours(), 24.0);
+}
+
+#[test]
+fn repeated_wall_clock_hours_get_their_own_positions() {
+    let fall_back = day("2024-11-03");
+    // 1:30 EDT, and an hour later 1:30 EST.
+    let events = vec![at(&fall_back, 1.5, 2.0), at(&fall_back, 2.5, 3.0)];
+    let segments = fall_back.localize_events(&events);
+
+    assert_eq!(segments[0].event.start, 1.5);
+    assert_eq!(segments[1].event.start, 2.5);
+    assert_eq!(fall_back.get_wall_clock_time(1.5), 1.5);
+    assert_eq!(fall_back.get_wall_clock_time(2.5), 1.5);
+    assert_eq!(fall_back.get_wall_clock_time(25.0), 0.0);
+
+    let day_events: Vec<Event> = segments.iter().map(|segment| segment.event).collect();
+    let mut tiler = Tiler::new(day_events, TilerOptions::new(0.0001), BasicTilingMethod::new()).unwrap();
+
+    tiler.tile().unwrap();
+
+    assert!(tiler.get_tiles_in_original_order().iter().all(|tile| tile.dx == 1.0));
+}
+
+#[test]
+fn skipped_wall_clock_hours_take_no_room() {
+    let spring_forward = day("2024-03-10");
+
+    assert_eq!(spring_forward.get_position(spring_forward.start + 3.0 * SECONDS_PER_HOUR), 3.0);
+    assert_eq!(spring_forward.get_wall_clock_time(2.0), 3.0);
+    assert_eq!(spring_forward.get_wall_clock_time(22.5), 23.5);
+}
+
+#[test]
+fn days_whose_midnight_is_skipped_start_after_the_gap() {
+    let santiago = DisplayDay::new("2024-09-08", "America/Santiago").unwrap();
+
+    assert_eq!(santiago.start, 1_725_768_000.0);
+    assert_eq!(santiago.get_hours(), 23.0);
+    assert_eq!(santiago.get_wall_clock_time(0.0), 1.0);
+}
+
+#[test]
+fn clips_events_to_the_day() {
+    let fall_back = day("2024-11-03");
+    let events = vec![
+        at(&fall_back, -2.0, 1.0),
+        at(&fall_back, 24.0, 26.0),
+        at(&fall_back, 25.0, 26.0),
+        at(&fall_back, -1.0, 0.0),
+        at(&fall_back, 0.0, 0.0),
+    ];
+    let segments: Vec<(usize, f64, f64, bool, bool)> = fall_back.localize_events(&events).iter()
+        .map(|segment| {
+            (segment.original_index, segment.event.start, segment.event.end, segment.continues_before, segment.continues_after)
+        })
+        .collect();
+
+    assert_eq!(segments, vec![
+        (0, 0.0, 1.0, true, false),
+        (1, 24.0, 25.0, false, true),
+        (4, 0.0, 0.0, false, false),
+    ]);
+}
+
+#[test]
+fn rejects_unknown_zones_and_dates() {
+    assert_eq!(
+        DisplayDay::new("2024-03-10", "Mars/Olympus_Mons").err(),
+        Some(TimeError::UnknownTimeZone { name: "Mars/Olympus_Mons".to_string() })
+    );
+    assert_eq!(
+        DisplayDay::new("2024-02-30", "UTC").err(),
+        Some(TimeError::InvalidDate { date: "2024-02-30".to_string() })
+    );
+}
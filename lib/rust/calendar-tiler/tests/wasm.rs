@@ -0,0 +1,54 @@
+use calendar_tiler::tiler_options::TilerOptions;
+use calendar_tiler::tiling_method::TilingMethodName;
+use calendar_tiler::wasm::{tile_event_buffers, EventBuffers};
+
+#[test]
+fn event_buffers_pack_tiles_in_original_order() {
+    let mut event_buffers = EventBuffers::new(3);
+
+    event_buffers.starts_mut().copy_from_slice(&[9.5, 9.0, 12.0]);
+    event_buffers.ends_mut().copy_from_slice(&[11.0, 10.5, 13.0]);
+    event_buffers.tile(TilingMethodName::Cascade, &TilerOptions::new(0.0001), None).unwrap();
+
+    assert_eq!(event_buffers.number_of_events(), 3);
+    assert_eq!(event_buffers.tiles(), &[
+        0.1, 0.9, 9.5, 1.5,
+        0.0, 1.0, 9.0, 1.5,
+        0.0, 1.0, 12.0, 1.0,
+    ]);
+    assert_eq!(event_buffers.z(), &[1, 0, 0]);
+}
+
+#[test]
+fn event_buffers_pack_as_tile_event_buffers_does() {
+    let starts = [10.0, 9.0, 9.25, 14.0];
+    let ends = [11.0, 10.5, 9.75, 15.0];
+    let mut event_buffers = EventBuffers::new(starts.len());
+    let mut tiles = vec![0.0; starts.len() * 4];
+
+    event_buffers.starts_mut().copy_from_slice(&starts);
+    event_buffers.ends_mut().copy_from_slice(&ends);
+    event_buffers.tile(TilingMethodName::SpaceFilling, &TilerOptions::new(0.0001), None).unwrap();
+    tile_event_buffers(&starts, &ends, TilingMethodName::SpaceFilling, &TilerOptions::new(0.0001), &mut tiles, None).unwrap();
+
+    assert_eq!(event_buffers.tiles(), tiles.as_slice());
+}
+
+#[test]
+fn event_buffers_are_reachable_through_their_pointers() {
+    let mut event_buffers = EventBuffers::new(2);
+
+    // JS reads and writes the same memory through typed array views.
+    unsafe {
+        std::slice::from_raw_parts_mut(event_buffers.starts_ptr(), 2).copy_from_slice(&[10.0, 9.0]);
+        std::slice::from_raw_parts_mut(event_buffers.ends_ptr(), 2).copy_from_slice(&[11.0, 10.5]);
+    }
+
+    event_buffers.tile(TilingMethodName::Basic, &TilerOptions::new(0.0001), None).unwrap();
+
+    let tiles = unsafe { std::slice::from_raw_parts(event_buffers.tiles_ptr(), 8) };
+
+    assert_eq!(&tiles[2 .. 4], &[10.0, 1.0]);
+    assert_eq!(&tiles[6 .. 8], &[9.0, 1.5]);
+    assert!(tiles[0] > tiles[4]);
+}
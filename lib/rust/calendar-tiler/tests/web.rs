@@ -11,3 +11,119 @@ wasm_bindgen_test_configure!(run_in_browser);
 fn pass() {
     assert_eq!(1 + 1, 2);
 }
+
+#[wasm_bindgen_test]
+fn tile_events_returns_tiles_in_original_order() {
+    use calendar_tiler::tiler_options::TilerOptions;
+    use calendar_tiler::tiling_method::TilingMethodName;
+    use calendar_tiler::wasm::{tile_events, WasmEvent};
+
+    let tiles = tile_events(
+        vec![
+            WasmEvent::new("late".to_string(), 10.0, 11.0),
+            WasmEvent::new("early".to_string(), 9.0, 10.5),
+        ],
+        TilingMethodName::Basic,
+        &TilerOptions::new(0.0001),
+        None
+    ).unwrap();
+
+    assert_eq!(tiles[0].id(), "late");
+    assert_eq!(tiles[0].y, 10.0);
+    assert_eq!(tiles[1].id(), "early");
+    assert_eq!(tiles[1].y, 9.0);
+    assert!(tiles[0].x > tiles[1].x);
+}
+
+#[wasm_bindgen_test]
+fn tile_events_puts_all_day_events_in_lanes() {
+    use calendar_tiler::tiler_options::TilerOptions;
+    use calendar_tiler::tiling_method::TilingMethodName;
+    use calendar_tiler::wasm::{tile_events, WasmEvent};
+
+    let mut offsite = WasmEvent::new("offsite".to_string(), 0.0, 24.0);
+
+    offsite.all_day = true;
+
+    let tiles = tile_events(
+        vec![offsite, WasmEvent::new("standup".to_string(), 9.0, 9.25)],
+        TilingMethodName::Basic,
+        &TilerOptions::new(0.0001),
+        None
+    ).unwrap();
+
+    assert!(tiles[0].all_day);
+    assert_eq!(tiles[0].lane, 0);
+    assert!(!tiles[1].all_day);
+    assert_eq!(tiles[1].dx, 1.0);
+}
+
+#[wasm_bindgen_test]
+fn tile_event_buffers_writes_interleaved_tiles() {
+    use calendar_tiler::tiler_options::TilerOptions;
+    use calendar_tiler::tiling_method::TilingMethodName;
+    use calendar_tiler::wasm::tile_event_buffers;
+
+    let mut tiles = vec![0.0; 8];
+
+    tile_event_buffers(
+        &[10.0, 9.0],
+        &[11.0, 10.5],
+        TilingMethodName::Basic,
+        &TilerOptions::new(0.0001),
+        &mut tiles,
+        None
+    ).unwrap();
+
+    assert_eq!(&tiles[2 .. 4], &[10.0, 1.0]);
+    assert_eq!(&tiles[6 .. 8], &[9.0, 1.5]);
+    assert!(tiles[0] > tiles[4]);
+}
+
+#[wasm_bindgen_test]
+fn tile_event_buffers_with_z_writes_the_stacking_order_apart() {
+    use calendar_tiler::tiler_options::TilerOptions;
+    use calendar_tiler::tiling_method::TilingMethodName;
+    use calendar_tiler::wasm::tile_event_buffers_with_z;
+
+    let mut tiles = vec![0.0; 8];
+    let mut z = vec![0; 2];
+
+    tile_event_buffers_with_z(
+        &[9.5, 9.0],
+        &[11.0, 10.5],
+        TilingMethodName::Cascade,
+        &TilerOptions::new(0.0001),
+        &mut tiles,
+        &mut z,
+        None
+    ).unwrap();
+
+    assert_eq!(z, vec![1, 0]);
+    assert_eq!(&tiles[2 .. 4], &[9.5, 1.5]);
+}
+
+#[wasm_bindgen_test]
+fn tile_events_ranks_auto_layouts_by_the_objective() {
+    use calendar_tiler::layout_quality::LayoutObjective;
+    use calendar_tiler::tiler_options::TilerOptions;
+    use calendar_tiler::tiling_method::{get_tiling_method_with_objective, TilingMethodName};
+    use calendar_tiler::wasm::{tile_events, WasmEvent};
+    use calendar_tiler::{Event, Tiler};
+
+    let spans = [(9.0, 12.0), (9.5, 10.0), (10.0, 10.5), (11.0, 13.0)];
+    let tiler_options = TilerOptions::new(0.0001);
+    let wasm_events = spans.iter().enumerate().map(|(i, &(start, end))| WasmEvent::new(i.to_string(), start, end)).collect();
+    let tiles = tile_events(wasm_events, TilingMethodName::Auto, &tiler_options, Some(LayoutObjective::AreaUtilization)).unwrap();
+    let mut tiler = Tiler::new(
+        spans.iter().map(|&(start, end)| Event::new(start, end)).collect(),
+        tiler_options,
+        get_tiling_method_with_objective(TilingMethodName::Auto, LayoutObjective::AreaUtilization)
+    ).unwrap();
+
+    tiler.tile().unwrap();
+
+    for (wasm_tile, tile) in tiles.iter().zip(tiler.get_tiles_in_original_order()) {
+        assert_eq!((wasm_tile.x, wasm_tile.dx), (tile.x, tile.dx));
+    }
+}
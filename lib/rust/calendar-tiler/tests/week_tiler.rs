@@ -0,0 +1,105 @@
+use calendar_tiler::week_tiler::{self, SECONDS_PER_DAY, SECONDS_PER_HOUR};
+use calendar_tiler::{
+    check_layout,
+    BasicTilingMethod,
+    Event,
+    InvalidEventReason,
+    Tile,
+    TilerError,
+    TilerOptions,
+    WeekTiler,
+};
+
+// A Monday midnight, in seconds since the Unix epoch.
+const WEEK_START: f64 = 1_704_067_200.0;
+
+fn at(day: f64, hour: f64) -> f64 {
+    WEEK_START + day * SECONDS_PER_DAY + hour * SECONDS_PER_HOUR
+}
+
+fn new_week_tiler(events: Vec<Event>) -> Result<WeekTiler<BasicTilingMethod>, TilerError> {
+    WeekTiler::new(events, WEEK_START, 7, TilerOptions::new(0.0001), BasicTilingMethod::new())
+}
+
+#[test]
+fn splits_events_that_cross_midnight() {
+    let days = week_tiler::split_into_days(&[Event::new(at(0.0, 22.0), at(2.0, 3.0))], WEEK_START, 7);
+    let segments: Vec<(usize, f64, f64, bool, bool)> = days.iter()
+        .flatten()
+        .map(|segment| (segment.day_index, segment.event.start, segment.event.end, segment.continues_before, segment.continues_after))
+        .collect();
+
+    assert_eq!(segments, vec![
+        (0, 22.0, 24.0, false, true),
+        (1, 0.0, 24.0, true, true),
+        (2, 0.0, 3.0, true, false),
+    ]);
+    assert!(days.iter().flatten().all(|segment| segment.original_index == 0));
+}
+
+#[test]
+fn keeps_events_ending_at_midnight_on_their_day() {
+    let days = week_tiler::split_into_days(&[
+        Event::new(at(1.0, 23.0), at(2.0, 0.0)),
+        Event::new(at(2.0, 0.0), at(2.0, 0.0)),
+    ], WEEK_START, 7);
+
+    assert_eq!(days[1].len(), 1);
+    assert!(!days[1][0].continues_after);
+    assert_eq!(days[2].len(), 1);
+    assert_eq!(days[2][0].original_index, 1);
+}
+
+#[test]
+fn drops_the_parts_of_events_outside_the_week() {
+    let days = week_tiler::split_into_days(&[
+        Event::new(at(-1.0, 20.0), at(0.0, 2.0)),
+        Event::new(at(6.0, 23.0), at(7.0, 1.0)),
+        Event::new(at(9.0, 9.0), at(9.0, 10.0)),
+    ], WEEK_START, 7);
+
+    assert_eq!(days.iter().flatten().count(), 2);
+    assert_eq!((days[0][0].event.start, days[0][0].continues_before), (0.0, true));
+    assert_eq!((days[6][0].event.end, days[6][0].continues_after), (24.0, true));
+}
+
+#[test]
+fn tiles_every_day_on_its_own() {
+    let events = vec![
+        Event::new(at(0.0, 23.0), at(1.0, 1.0)),
+        Event::new(at(0.0, 22.0), at(0.0, 23.5)),
+        Event::new(at(1.0, 9.0), at(1.0, 10.0)),
+    ];
+    let mut week_tiler = new_week_tiler(events).unwrap();
+
+    week_tiler.tile().unwrap();
+
+    let tiles: Vec<(usize, usize, f64, f64)> = week_tiler.tiles.iter()
+        .map(|week_tile| (week_tile.day_index, week_tile.original_index, week_tile.tile.dx, week_tile.tile.y))
+        .collect();
+
+    assert_eq!(tiles, vec![(0, 0, 0.5, 23.0), (0, 1, 0.5, 22.0), (1, 0, 1.0, 0.0), (1, 2, 1.0, 9.0)]);
+
+    for (day_index, segments) in week_tiler.days.iter().enumerate() {
+        let day_tiles: Vec<Tile> = week_tiler.tiles.iter()
+            .filter(|week_tile| week_tile.day_index == day_index)
+            .map(|week_tile| week_tile.tile)
+            .collect();
+        let day_events: Vec<Event> = segments.iter().map(|segment| segment.event).collect();
+
+        assert!(check_layout(&day_tiles, &day_events, &week_tiler.tiler_options).is_empty());
+    }
+}
+
+#[test]
+fn rejects_invalid_events() {
+    let result = new_week_tiler(vec![
+        Event::new(at(0.0, 9.0), at(0.0, 10.0)),
+        Event::new(at(0.0, 11.0), at(0.0, 10.0)),
+    ]);
+
+    assert_eq!(result.err(), Some(TilerError::InvalidEvent {
+        original_index: 1,
+        reason: InvalidEventReason::EndBeforeStart
+    }));
+}